@@ -17,11 +17,29 @@ mod tests;
 #[ink::contract]
 mod fungibles {
     use super::*;
+    use ink::storage::Mapping;
+
+    /// Balance of an account, or total supply, after the last change made in `block`.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct Checkpoint {
+        pub block: BlockNumber,
+        pub value: Balance,
+    }
 
     #[ink(storage)]
     pub struct Fungible {
         id: TokenId,
         owner: AccountId,
+        /// Balance checkpoints of each account, by position.
+        balance_checkpoints: Mapping<(AccountId, u32), Checkpoint>,
+        /// Number of balance checkpoints of each account.
+        balance_checkpoint_counts: Mapping<AccountId, u32>,
+        /// Total supply checkpoints, by position.
+        supply_checkpoints: Mapping<u32, Checkpoint>,
+        /// Number of total supply checkpoints.
+        supply_checkpoint_count: u32,
     }
 
     impl Fungible {
@@ -38,6 +56,10 @@ mod fungibles {
             let instance = Self {
                 id,
                 owner: Self::env().caller(),
+                balance_checkpoints: Mapping::default(),
+                balance_checkpoint_counts: Mapping::default(),
+                supply_checkpoints: Mapping::default(),
+                supply_checkpoint_count: 0,
             };
             let contract_id = instance.env().account_id();
             api::create(id, contract_id, min_balance).map_err(Psp22Error::from)?;
@@ -98,6 +120,8 @@ mod fungibles {
                 return Ok(());
             }
             api::transfer(self.id, to, value).map_err(Psp22Error::from)?;
+            self.checkpoint_balance(contract);
+            self.checkpoint_balance(to);
             self.env().emit_event(Transfer {
                 from: Some(contract),
                 to: Some(to),
@@ -132,6 +156,8 @@ mod fungibles {
             // A successful transfer reduces the allowance from `from` to the contract and triggers
             // an `Approval` event with the updated allowance amount.
             api::transfer_from(self.id, from, to, value).map_err(Psp22Error::from)?;
+            self.checkpoint_balance(from);
+            self.checkpoint_balance(to);
             self.env().emit_event(Transfer {
                 from: Some(contract),
                 to: Some(to),
@@ -266,6 +292,8 @@ mod fungibles {
                 return Ok(());
             }
             api::mint(self.id, account, value).map_err(Psp22Error::from)?;
+            self.checkpoint_balance(account);
+            self.checkpoint_supply();
             self.env().emit_event(Transfer {
                 from: None,
                 to: Some(account),
@@ -289,6 +317,8 @@ mod fungibles {
                 return Ok(());
             }
             api::burn(self.id, account, value).map_err(Psp22Error::from)?;
+            self.checkpoint_balance(account);
+            self.checkpoint_supply();
             self.env().emit_event(Transfer {
                 from: Some(account),
                 to: None,
@@ -317,5 +347,101 @@ mod fungibles {
             self.owner = owner;
             Ok(())
         }
+
+        /// Returns the balance of `owner` at the end of `block`. Only the transfers, mints and
+        /// burns made through the contract are recorded, so the balance of the current block
+        /// can still change.
+        ///
+        /// # Parameters
+        /// - `owner` - The account whose balance is being queried.
+        /// - `block` - The block at which the balance is queried.
+        #[ink(message)]
+        pub fn balance_of_at(&self, owner: AccountId, block: BlockNumber) -> Balance {
+            let count = self
+                .balance_checkpoint_counts
+                .get(owner)
+                .unwrap_or_default();
+            value_at(count, block, |position| {
+                self.balance_checkpoints.get((owner, position))
+            })
+        }
+
+        /// Returns the total token supply at the end of `block`. Only the mints and burns made
+        /// through the contract are recorded.
+        ///
+        /// # Parameters
+        /// - `block` - The block at which the total supply is queried.
+        #[ink(message)]
+        pub fn total_supply_at(&self, block: BlockNumber) -> Balance {
+            value_at(self.supply_checkpoint_count, block, |position| {
+                self.supply_checkpoints.get(position)
+            })
+        }
+
+        /// Record the current balance of `account`.
+        fn checkpoint_balance(&mut self, account: AccountId) {
+            let count = self
+                .balance_checkpoint_counts
+                .get(account)
+                .unwrap_or_default();
+            let checkpoint = Checkpoint {
+                block: self.env().block_number(),
+                value: self.balance_of(account),
+            };
+            let last = count
+                .checked_sub(1)
+                .and_then(|position| self.balance_checkpoints.get((account, position)));
+            let position = write_position(count, last, checkpoint.block);
+            self.balance_checkpoints
+                .insert((account, position), &checkpoint);
+            self.balance_checkpoint_counts
+                .insert(account, &(position + 1));
+        }
+
+        /// Record the current total supply.
+        fn checkpoint_supply(&mut self) {
+            let count = self.supply_checkpoint_count;
+            let checkpoint = Checkpoint {
+                block: self.env().block_number(),
+                value: self.total_supply(),
+            };
+            let last = count
+                .checked_sub(1)
+                .and_then(|position| self.supply_checkpoints.get(position));
+            let position = write_position(count, last, checkpoint.block);
+            self.supply_checkpoints.insert(position, &checkpoint);
+            self.supply_checkpoint_count = position + 1;
+        }
+    }
+
+    /// Position of a checkpoint made in `block`, among `count` checkpoints ending with `last`.
+    /// A block keeps a single checkpoint, holding the value after its last change.
+    fn write_position(count: u32, last: Option<Checkpoint>, block: BlockNumber) -> u32 {
+        match last {
+            Some(last) if last.block == block => count - 1,
+            _ => count,
+        }
+    }
+
+    /// Returns the value of the last checkpoint made at or before `block`, among `count`
+    /// checkpoints ordered by block, or zero if there is none.
+    fn value_at(
+        count: u32,
+        block: BlockNumber,
+        checkpoint: impl Fn(u32) -> Option<Checkpoint>,
+    ) -> Balance {
+        // Binary search for the first checkpoint made after `block`.
+        let (mut low, mut high) = (0, count);
+        while low < high {
+            let middle = low + (high - low) / 2;
+            match checkpoint(middle) {
+                Some(checkpoint) if checkpoint.block <= block => low = middle + 1,
+                _ => high = middle,
+            }
+        }
+        low.checked_sub(1)
+            .and_then(checkpoint)
+            .map(|checkpoint| checkpoint.value)
+            .unwrap_or_default()
     }
 }
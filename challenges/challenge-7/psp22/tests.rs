@@ -11,7 +11,7 @@ use drink::{
 	},
 	last_contract_event,
 	session::Session,
-	AssetsAPI, TestExternalities, NO_SALT,
+	AssetsAPI, SystemAPI, TestExternalities, NO_SALT,
 };
 use ink::scale::Encode;
 use pop_api::{
@@ -633,6 +633,33 @@ fn burn_works(mut session: Session) {
 	);
 }
 
+// Checkpoint tests.
+
+#[drink::test(sandbox = Pop)]
+fn checkpoints_work(mut session: Session) {
+	let _ = env_logger::try_init();
+	// Deploy a new contract.
+	deploy_with_default(&mut session).unwrap();
+	session.set_actor(ALICE);
+	// Mint tokens, twice in the same block.
+	let minted_at = session.sandbox().block_number();
+	assert_ok!(mint(&mut session, ALICE, AMOUNT));
+	assert_ok!(mint(&mut session, ALICE, AMOUNT));
+	// Burn part of them in a later block.
+	session.sandbox().build_blocks(2);
+	let burned_at = session.sandbox().block_number();
+	assert_ok!(burn(&mut session, ALICE, 1));
+	// A block keeps the value after its last change.
+	assert_eq!(balance_of_at(&mut session, ALICE, minted_at), AMOUNT * 2);
+	assert_eq!(total_supply_at(&mut session, minted_at), AMOUNT * 2);
+	// Blocks without changes keep the value of the previous checkpoint.
+	assert_eq!(balance_of_at(&mut session, ALICE, burned_at - 1), AMOUNT * 2);
+	assert_eq!(balance_of_at(&mut session, ALICE, burned_at), AMOUNT * 2 - 1);
+	assert_eq!(total_supply_at(&mut session, burned_at + 10), AMOUNT * 2 - 1);
+	// Accounts without checkpoints hold nothing.
+	assert_eq!(balance_of_at(&mut session, BOB, burned_at), 0);
+}
+
 // Deploy the contract with `NO_SALT and `INIT_VALUE`.
 fn deploy(
 	session: &mut Session<Pop>,
@@ -661,6 +688,21 @@ fn balance_of(session: &mut Session<Pop>, owner: AccountId) -> Balance {
 		.unwrap()
 }
 
+fn balance_of_at(session: &mut Session<Pop>, owner: AccountId, block: u32) -> Balance {
+	call::<Pop, Balance, Psp22Error>(
+		session,
+		"balance_of_at",
+		vec![owner.to_string(), block.to_string()],
+		None,
+	)
+	.unwrap()
+}
+
+fn total_supply_at(session: &mut Session<Pop>, block: u32) -> Balance {
+	call::<Pop, Balance, Psp22Error>(session, "total_supply_at", vec![block.to_string()], None)
+		.unwrap()
+}
+
 fn allowance(session: &mut Session<Pop>, owner: AccountId, spender: AccountId) -> Balance {
 	call::<Pop, Balance, Psp22Error>(
 		session,
@@ -0,0 +1,821 @@
+use drink::{
+	assert_ok, call,
+	devnet::{AccountId, Balance, Runtime},
+	mock_message,
+	session::{mocking_api::MockingApi, Session},
+	AssetsAPI, ContractMock, SystemAPI, TestExternalities, NO_SALT,
+};
+use minidao_common::{Ballot, DaoError, ProposalStatus, TokenError};
+use pop_api::v0::fungibles::Psp22Error;
+
+const UNIT: Balance = 10_000_000_000;
+const INIT_AMOUNT: Balance = 100_000_000 * UNIT;
+const INIT_VALUE: Balance = 100 * UNIT;
+const ALICE: AccountId = AccountId::new([1u8; 32]);
+const BOB: AccountId = AccountId::new([2_u8; 32]);
+const CHARLIE: AccountId = AccountId::new([3_u8; 32]);
+const MIN_BALANCE: Balance = 1;
+const TOKEN: u32 = 1;
+const VOTING_PERIOD: u32 = 10;
+const MINT_AMOUNT: Balance = 100;
+const SUPERDAO_PROPOSAL: u32 = 7;
+
+// The contract bundle provider.
+//
+// See https://github.com/r0gue-io/pop-drink/blob/main/crates/drink/drink/test-macro/src/lib.rs for more information.
+#[drink::contract_bundle_provider]
+enum BundleProvider {}
+
+/// Sandbox environment for Pop Devnet Runtime.
+pub struct Pop {
+	ext: TestExternalities,
+}
+
+impl Default for Pop {
+	fn default() -> Self {
+		// Initialising genesis state, providing accounts with an initial balance.
+		let balances: Vec<(AccountId, u128)> =
+			vec![(ALICE, INIT_AMOUNT), (BOB, INIT_AMOUNT), (CHARLIE, INIT_AMOUNT)];
+		let ext = BlockBuilder::<Runtime>::new_ext(balances);
+		Self { ext }
+	}
+}
+
+// Implement core functionalities for the `Pop` sandbox.
+drink::impl_sandbox!(Pop, Runtime, ALICE);
+
+// Deployment tests.
+
+#[drink::test(sandbox = Pop)]
+fn new_with_asset_works(mut session: Session) {
+	let _ = env_logger::try_init();
+	let superdao = deploy_superdao(&mut session);
+	assert!(!session.sandbox().asset_exists(&TOKEN));
+	assert_ok!(deploy_with_default(&mut session, superdao));
+	// The DAO votes with the asset it created.
+	assert!(session.sandbox().asset_exists(&TOKEN));
+	assert_eq!(session.sandbox().total_supply(&TOKEN), 0);
+	assert_eq!(
+		call::<Pop, minidao::dao::VotingToken, DaoError>(
+			&mut session,
+			"get_voting_token",
+			vec![],
+			None
+		),
+		Ok(minidao::dao::VotingToken::Asset(TOKEN))
+	);
+	// The asset can't be created twice.
+	let superdao = deploy_superdao(&mut session);
+	assert!(deploy_with_default(&mut session, superdao).is_err());
+}
+
+// Voter registration tests.
+
+#[drink::test(sandbox = Pop)]
+fn register_voter_mints_once_works(mut session: Session) {
+	let _ = env_logger::try_init();
+	let superdao = deploy_superdao(&mut session);
+	assert_ok!(deploy_with_default(&mut session, superdao));
+	// Registering mints the voting tokens of the voter.
+	assert_ok!(register_voter(&mut session));
+	assert_eq!(session.sandbox().balance_of(&TOKEN, &ALICE), MINT_AMOUNT);
+	// Registering again after deregistering doesn't mint more tokens.
+	assert_ok!(call::<Pop, (), DaoError>(&mut session, "deregister_voter", vec![], None));
+	assert_ok!(register_voter(&mut session));
+	assert_eq!(session.sandbox().balance_of(&TOKEN, &ALICE), MINT_AMOUNT);
+	assert_eq!(session.sandbox().total_supply(&TOKEN), MINT_AMOUNT);
+}
+
+// Governance action tests.
+
+#[drink::test(sandbox = Pop)]
+fn join_and_leave_superdao_through_proposals_works(mut session: Session) {
+	let _ = env_logger::try_init();
+	let superdao = deploy_superdao(&mut session);
+	assert_ok!(deploy_with_default(&mut session, superdao));
+	assert_ok!(register_voter(&mut session));
+	assert!(!is_superdao_member(&mut session));
+	// The executed proposal registers the DAO, which `execute` keeps.
+	let proposal_id = propose_superdao_membership(&mut session, "propose_join_superdao").unwrap();
+	pass_proposal(&mut session, proposal_id);
+	assert_ok!(execute(&mut session, proposal_id));
+	assert!(is_superdao_member(&mut session));
+	assert_eq!(proposal_status(&mut session, proposal_id), ProposalStatus::Executed);
+	// A second proposal can only leave, as the DAO is a member.
+	let proposal_id = propose_superdao_membership(&mut session, "propose_join_superdao").unwrap();
+	pass_proposal(&mut session, proposal_id);
+	assert_eq!(execute(&mut session, proposal_id), Err(DaoError::AlreadySuperDaoMember));
+	let proposal_id = propose_superdao_membership(&mut session, "propose_leave_superdao").unwrap();
+	pass_proposal(&mut session, proposal_id);
+	assert_ok!(execute(&mut session, proposal_id));
+	assert!(!is_superdao_member(&mut session));
+}
+
+#[drink::test(sandbox = Pop)]
+fn migrate_superdao_through_proposal_works(mut session: Session) {
+	let _ = env_logger::try_init();
+	let old = deploy_superdao(&mut session);
+	let new = deploy_superdao(&mut session);
+	assert_ok!(deploy_with_default(&mut session, old.clone()));
+	assert_ok!(register_voter(&mut session));
+	let proposal_id = propose_action(&mut session, "JoinSuperDao").unwrap();
+	pass_proposal(&mut session, proposal_id);
+	assert_ok!(execute(&mut session, proposal_id));
+	// The DAO moves to the new Super DAO, where it registers as it was a member of the old one.
+	let proposal_id = propose_action(&mut session, &format!("MigrateSuperDao({new})")).unwrap();
+	pass_proposal(&mut session, proposal_id);
+	assert_ok!(execute(&mut session, proposal_id));
+	assert_eq!(get_superdao(&mut session), new);
+	assert!(is_superdao_member(&mut session));
+	// The migration can't be executed again.
+	assert_eq!(execute(&mut session, proposal_id), Err(DaoError::ProposalNotSucceeded));
+}
+
+#[drink::test(sandbox = Pop)]
+fn migrate_superdao_keeps_prevotes_apart_works(mut session: Session) {
+	let _ = env_logger::try_init();
+	let old = deploy_superdao(&mut session);
+	let new = deploy_superdao(&mut session);
+	let dao = deploy_with_default(&mut session, old.clone()).unwrap();
+	assert_ok!(register_voter(&mut session));
+	assert_ok!(create_prevote(&mut session));
+	assert_ok!(session.sandbox().approve(&TOKEN, &ALICE, &dao, 60));
+	assert_ok!(submit_prevote(&mut session, true, 60, 0));
+	let proposal_id = propose_action(&mut session, &format!("MigrateSuperDao({new})")).unwrap();
+	pass_proposal(&mut session, proposal_id);
+	assert_ok!(execute(&mut session, proposal_id));
+	// The new Super DAO reuses the proposal ID, whose prevote starts afresh.
+	assert_eq!(create_prevote(&mut session), Ok(SUPERDAO_PROPOSAL));
+	assert_eq!(locked_balance(&mut session, ALICE), 0);
+	assert_ok!(session.sandbox().approve(&TOKEN, &ALICE, &dao, 40));
+	assert_ok!(submit_prevote(&mut session, false, 40, 0));
+	let prevote = get_prevote(&mut session);
+	assert_eq!((prevote.aye_weight, prevote.nay_weight, prevote.voter_count), (0, 40, 1));
+	// The tokens locked on the old Super DAO can still be withdrawn, once.
+	assert_eq!(withdraw_locked(&mut session), Err(DaoError::VotingPeriodNotEnded));
+	assert_ok!(withdraw_locked_from(&mut session, old.clone()));
+	assert_eq!(session.sandbox().balance_of(&TOKEN, &ALICE), 60);
+	assert_eq!(withdraw_locked_from(&mut session, old), Err(DaoError::NotVoted));
+	assert_eq!(locked_balance(&mut session, ALICE), 40);
+}
+
+// Prevote tests.
+
+#[drink::test(sandbox = Pop)]
+fn submit_prevote_escrows_tokens_works(mut session: Session) {
+	let _ = env_logger::try_init();
+	let superdao = deploy_superdao(&mut session);
+	let dao = deploy_with_default(&mut session, superdao).unwrap();
+	register_voters(&mut session, &[ALICE, BOB]);
+	assert_eq!(create_prevote(&mut session), Ok(SUPERDAO_PROPOSAL));
+	// The DAO needs an allowance to escrow the tokens.
+	assert_eq!(
+		submit_prevote(&mut session, true, 60, 0),
+		Err(DaoError::Token(TokenError::InsufficientAllowance))
+	);
+	assert_ok!(session.sandbox().approve(&TOKEN, &ALICE, &dao, 60));
+	assert_ok!(submit_prevote(&mut session, true, 60, 0));
+	assert_eq!(session.sandbox().balance_of(&TOKEN, &ALICE), MINT_AMOUNT - 60);
+	assert_eq!(session.sandbox().balance_of(&TOKEN, &dao), 60);
+	assert_eq!(locked_balance(&mut session, ALICE), 60);
+	// The escrowed tokens can't back another prevote.
+	assert_eq!(submit_prevote(&mut session, true, 60, 0), Err(DaoError::AlreadyVoted));
+	session.set_actor(BOB);
+	assert_ok!(session.sandbox().approve(&TOKEN, &BOB, &dao, 30));
+	assert_ok!(submit_prevote(&mut session, false, 30, 0));
+	let prevote = get_prevote(&mut session);
+	assert_eq!((prevote.aye_weight, prevote.nay_weight, prevote.voter_count), (60, 30, 2));
+	// The tokens are returned once the prevote ends, and only once.
+	assert_eq!(withdraw_locked(&mut session), Err(DaoError::VotingPeriodNotEnded));
+	session.sandbox().build_blocks(VOTING_PERIOD);
+	assert_ok!(withdraw_locked(&mut session));
+	assert_eq!(session.sandbox().balance_of(&TOKEN, &BOB), MINT_AMOUNT);
+	assert_eq!(session.sandbox().balance_of(&TOKEN, &dao), 60);
+	assert_eq!(withdraw_locked(&mut session), Err(DaoError::NotVoted));
+	session.set_actor(ALICE);
+	assert_ok!(withdraw_locked(&mut session));
+	assert_eq!(session.sandbox().balance_of(&TOKEN, &ALICE), MINT_AMOUNT);
+	assert_eq!(locked_balance(&mut session, ALICE), 0);
+}
+
+#[drink::test(sandbox = Pop)]
+fn undelegate_after_prevote_works(mut session: Session) {
+	let _ = env_logger::try_init();
+	let superdao = deploy_superdao(&mut session);
+	let dao = deploy_with_default(&mut session, superdao).unwrap();
+	register_voters(&mut session, &[ALICE, BOB]);
+	// Bob delegates all his tokens to Alice, which the DAO escrows.
+	session.set_actor(BOB);
+	assert_ok!(session.sandbox().approve(&TOKEN, &BOB, &dao, MINT_AMOUNT));
+	assert_ok!(call::<Pop, (), DaoError>(&mut session, "delegate", vec![ALICE.to_string()], None));
+	assert_eq!(session.sandbox().balance_of(&TOKEN, &BOB), 0);
+	// Alice prevotes with her tokens and those delegated to her.
+	session.set_actor(ALICE);
+	assert_ok!(create_prevote(&mut session));
+	assert_ok!(session.sandbox().approve(&TOKEN, &ALICE, &dao, 50));
+	assert_ok!(submit_prevote(&mut session, true, 50, 0));
+	assert_eq!(get_prevote(&mut session).aye_weight, 50 + MINT_AMOUNT);
+	// Bob can't take the tokens back while the prevote counts them.
+	session.set_actor(BOB);
+	assert_eq!(undelegate(&mut session), Err(DaoError::DelegationInUse));
+	session.sandbox().build_blocks(VOTING_PERIOD);
+	assert_ok!(undelegate(&mut session));
+	assert_eq!(session.sandbox().balance_of(&TOKEN, &BOB), MINT_AMOUNT);
+	assert_eq!(undelegate(&mut session), Err(DaoError::NotDelegated));
+}
+
+#[drink::test(sandbox = Pop)]
+fn voting_power_weighted_by_strategy_works(mut session: Session) {
+	let _ = env_logger::try_init();
+	let superdao = deploy_superdao(&mut session);
+	let dao = deploy_with_strategy(&mut session, superdao, "Quadratic").unwrap();
+	register_voters(&mut session, &[ALICE, BOB]);
+	assert_eq!(voting_power(&mut session, ALICE), Ok(10));
+	// The delegated tokens are weighted along with those of the delegate.
+	session.set_actor(BOB);
+	assert_ok!(session.sandbox().approve(&TOKEN, &BOB, &dao, MINT_AMOUNT));
+	assert_ok!(call::<Pop, (), DaoError>(&mut session, "delegate", vec![ALICE.to_string()], None));
+	assert_eq!(voting_power(&mut session, ALICE), Ok(14));
+	assert_eq!(voting_power(&mut session, BOB), Ok(0));
+}
+
+#[drink::test(sandbox = Pop)]
+fn prevote_weighted_by_conviction_works(mut session: Session) {
+	let _ = env_logger::try_init();
+	let superdao = deploy_superdao(&mut session);
+	let dao = deploy_with_strategy(&mut session, superdao, "Conviction").unwrap();
+	register_voters(&mut session, &[ALICE, BOB]);
+	assert_ok!(create_prevote(&mut session));
+	// Locking the tokens for two more voting periods triples the weight of the vote.
+	assert_ok!(session.sandbox().approve(&TOKEN, &ALICE, &dao, 50));
+	assert_ok!(submit_prevote(&mut session, true, 50, 2));
+	session.set_actor(BOB);
+	assert_ok!(session.sandbox().approve(&TOKEN, &BOB, &dao, 100));
+	assert_ok!(submit_prevote(&mut session, false, 100, 0));
+	let prevote = get_prevote(&mut session);
+	assert_eq!((prevote.aye_weight, prevote.nay_weight), (150, 100));
+	// The tokens locked without conviction unlock at the deadline, the others later.
+	assert_eq!(withdraw_locked(&mut session), Err(DaoError::VotingPeriodNotEnded));
+	session.sandbox().build_blocks(VOTING_PERIOD);
+	assert_ok!(withdraw_locked(&mut session));
+	assert_eq!(session.sandbox().balance_of(&TOKEN, &BOB), MINT_AMOUNT);
+	assert_eq!(withdraw_locked(&mut session), Err(DaoError::NotVoted));
+	session.set_actor(ALICE);
+	assert_eq!(withdraw_locked(&mut session), Err(DaoError::TokensLocked));
+	session.sandbox().build_blocks(2 * VOTING_PERIOD);
+	assert_ok!(withdraw_locked(&mut session));
+	assert_eq!(session.sandbox().balance_of(&TOKEN, &ALICE), MINT_AMOUNT);
+	assert_eq!(locked_balance(&mut session, ALICE), 0);
+}
+
+#[drink::test(sandbox = Pop)]
+fn finalize_prevote_rejects_tie_works(mut session: Session) {
+	let _ = env_logger::try_init();
+	let superdao = deploy_superdao(&mut session);
+	let dao = deploy_with_default(&mut session, superdao).unwrap();
+	register_voters(&mut session, &[ALICE, BOB]);
+	assert_ok!(create_prevote(&mut session));
+	assert_ok!(session.sandbox().approve(&TOKEN, &ALICE, &dao, 40));
+	assert_ok!(submit_prevote(&mut session, true, 40, 0));
+	session.set_actor(BOB);
+	assert_ok!(session.sandbox().approve(&TOKEN, &BOB, &dao, 40));
+	assert_ok!(submit_prevote(&mut session, false, 40, 0));
+	// The approvals must be more than the disapprovals.
+	session.sandbox().build_blocks(VOTING_PERIOD);
+	assert_eq!(finalize_prevote(&mut session), Ok(Ballot::Nay));
+	assert_eq!(prevote_result(&mut session), Some(Ballot::Nay));
+}
+
+#[drink::test(sandbox = Pop)]
+fn prevote_closes_before_superdao_works(mut session: Session) {
+	let _ = env_logger::try_init();
+	// The Super DAO closes its proposals before the voting period of the DAO ends.
+	let voting_period_end = session.sandbox().block_number() + VOTING_PERIOD / 2;
+	let superdao = deploy_superdao_closing_at(&mut session, voting_period_end);
+	assert_ok!(deploy_with_default(&mut session, superdao));
+	assert_ok!(register_voter(&mut session));
+	assert_ok!(create_prevote(&mut session));
+	// The prevote closes the block before, so that the DAO vote can still be cast.
+	assert_eq!(get_prevote(&mut session).deadline, voting_period_end - 1);
+	session.sandbox().build_blocks(VOTING_PERIOD / 2 - 1);
+	assert_eq!(finalize_prevote(&mut session), Ok(Ballot::Abstain));
+}
+
+#[drink::test(sandbox = Pop)]
+fn retract_prevote_keeps_voters_paginated_works(mut session: Session) {
+	let _ = env_logger::try_init();
+	let superdao = deploy_superdao(&mut session);
+	let dao = deploy_with_default(&mut session, superdao).unwrap();
+	register_voters(&mut session, &[ALICE, BOB, CHARLIE]);
+	assert_ok!(create_prevote(&mut session));
+	for (voter, amount) in [(ALICE, 10), (BOB, 20), (CHARLIE, 30)] {
+		session.set_actor(voter.clone());
+		assert_ok!(session.sandbox().approve(&TOKEN, &voter, &dao, amount));
+		assert_ok!(submit_prevote(&mut session, true, amount, 0));
+	}
+	assert_eq!(prevote_voters(&mut session, 0, 10), vec![ALICE, BOB, CHARLIE]);
+	// The last voter takes the position of the one retracting, who gets the tokens back.
+	session.set_actor(ALICE);
+	assert_ok!(call::<Pop, (), DaoError>(
+		&mut session,
+		"retract_prevote",
+		vec![SUPERDAO_PROPOSAL.to_string()],
+		None
+	));
+	assert_eq!(session.sandbox().balance_of(&TOKEN, &ALICE), MINT_AMOUNT);
+	assert_eq!(prevote_voters(&mut session, 0, 10), vec![CHARLIE, BOB]);
+	assert_eq!(prevote_voters(&mut session, 0, 1), vec![CHARLIE]);
+	assert_eq!(prevote_voters(&mut session, 1, 1), vec![BOB]);
+	assert_eq!(prevote_voters(&mut session, 2, 1), vec![]);
+	let prevote = get_prevote(&mut session);
+	assert_eq!((prevote.aye_weight, prevote.voter_count), (50, 2));
+	// The voter can prevote again, at the end of the voters.
+	assert_ok!(session.sandbox().approve(&TOKEN, &ALICE, &dao, 10));
+	assert_ok!(submit_prevote(&mut session, false, 10, 0));
+	assert_eq!(prevote_voters(&mut session, 0, 10), vec![CHARLIE, BOB, ALICE]);
+}
+
+#[drink::test(sandbox = Pop)]
+fn finalize_prevote_once_works(mut session: Session) {
+	let _ = env_logger::try_init();
+	let superdao = deploy_superdao(&mut session);
+	let dao = deploy_with_default(&mut session, superdao).unwrap();
+	register_voters(&mut session, &[ALICE, BOB]);
+	assert_ok!(create_prevote(&mut session));
+	assert_ok!(session.sandbox().approve(&TOKEN, &ALICE, &dao, 40));
+	assert_ok!(submit_prevote(&mut session, true, 40, 0));
+	session.set_actor(BOB);
+	assert_ok!(session.sandbox().approve(&TOKEN, &BOB, &dao, 30));
+	assert_ok!(submit_prevote(&mut session, false, 30, 0));
+	// The prevote is decided by weight after the deadline, and cast on the Super DAO once.
+	assert_eq!(finalize_prevote(&mut session), Err(DaoError::VotingPeriodNotEnded));
+	assert_eq!(prevote_result(&mut session), None);
+	session.sandbox().build_blocks(VOTING_PERIOD);
+	assert_eq!(finalize_prevote(&mut session), Ok(Ballot::Aye));
+	assert_eq!(prevote_result(&mut session), Some(Ballot::Aye));
+	assert_eq!(finalize_prevote(&mut session), Err(DaoError::ProposalAlreadyFinalized));
+	assert_eq!(prevote_result(&mut session), Some(Ballot::Aye));
+}
+
+#[drink::test(sandbox = Pop)]
+fn prevote_with_token_contract_escrows_tokens_works(mut session: Session) {
+	let _ = env_logger::try_init();
+	let superdao = deploy_superdao(&mut session);
+	let (dao, token) = deploy_with_token(&mut session, superdao);
+	// The token contract owned by the DAO mints the tokens of the voters.
+	assert_ok!(register_voter(&mut session));
+	assert_eq!(session.sandbox().balance_of(&TOKEN, &ALICE), MINT_AMOUNT);
+	// The prevote snapshots the balances of the block before its creation.
+	session.sandbox().build_blocks(1);
+	assert_ok!(create_prevote(&mut session));
+	// Tokens minted after the snapshot can't prevote.
+	session.set_actor(BOB);
+	assert_ok!(register_voter(&mut session));
+	assert_ok!(session.sandbox().approve(&TOKEN, &BOB, &token, 60));
+	assert_eq!(submit_prevote(&mut session, true, 60, 0), Err(DaoError::ExceedsSnapshotBalance));
+	session.set_actor(ALICE);
+	// The token contract, which voters allow to transfer their tokens, holds them for the DAO.
+	assert_ok!(session.sandbox().approve(&TOKEN, &ALICE, &token, 60));
+	assert_ok!(submit_prevote(&mut session, true, 60, 0));
+	assert_eq!(session.sandbox().balance_of(&TOKEN, &ALICE), MINT_AMOUNT - 60);
+	assert_eq!(session.sandbox().balance_of(&TOKEN, &token), 60);
+	assert_eq!(session.sandbox().balance_of(&TOKEN, &dao), 0);
+	assert_eq!(get_prevote(&mut session).aye_weight, 60);
+	// The tokens are returned once the prevote ends.
+	session.sandbox().build_blocks(VOTING_PERIOD);
+	assert_ok!(withdraw_locked(&mut session));
+	assert_eq!(session.sandbox().balance_of(&TOKEN, &ALICE), MINT_AMOUNT);
+	assert_eq!(session.sandbox().balance_of(&TOKEN, &token), 0);
+}
+
+// Cross-chain proposal tests.
+
+#[drink::test(sandbox = Pop)]
+fn xcm_proposal_is_mapped_to_local_record_works(mut session: Session) {
+	let _ = env_logger::try_init();
+	let superdao = deploy_superdao(&mut session);
+	assert_ok!(deploy_with_default(&mut session, superdao.clone()));
+	assert_ok!(register_voter(&mut session));
+	// Cross-chain proposals get a local ID, mapped from the ID of the Super DAO proposal.
+	assert_eq!(create_xcm_proposal(&mut session), Ok(0));
+	let proposal = get_cross_chain_proposal(&mut session, 0).unwrap();
+	assert_eq!(proposal.superdao, superdao);
+	assert_eq!(proposal.superdao_id, SUPERDAO_PROPOSAL);
+	assert_eq!(proposal.chain_id, 1000);
+	assert_eq!(proposal.status, ProposalStatus::Active);
+	assert_eq!(
+		call::<Pop, Option<u32>, DaoError>(
+			&mut session,
+			"get_cross_chain_proposal_id",
+			vec![SUPERDAO_PROPOSAL.to_string()],
+			None,
+		),
+		Ok(Some(0))
+	);
+	assert_eq!(
+		call::<Pop, Result<u32, DaoError>, DaoError>(
+			&mut session,
+			"create_superdao_cross_chain_proposal",
+			vec![1000.to_string(), "[1, 2, 3, 4]".to_string()],
+			None,
+		)
+		.and_then(|result| result),
+		Ok(1)
+	);
+	// The proposal syncs with the status of the Super DAO proposal.
+	assert_eq!(sync_proposal(&mut session, 0), Ok(ProposalStatus::Succeeded));
+	assert_eq!(
+		get_cross_chain_proposal(&mut session, 0).map(|proposal| proposal.status),
+		Ok(ProposalStatus::Succeeded)
+	);
+	assert_eq!(get_cross_chain_proposal(&mut session, 2), Err(DaoError::ProposalDoesNotExist));
+}
+
+// Deploy a mock of the Super DAO, accepting the DAO as a member and its votes. Proposals created
+// get the ID `SUPERDAO_PROPOSAL`, and are approved.
+fn deploy_superdao(session: &mut Session<Pop>) -> AccountId {
+	deploy_superdao_closing_at(session, u32::MAX)
+}
+
+// Deploy a mock of the Super DAO as `deploy_superdao` does, closing the voting period of its
+// proposals at `voting_period_end`.
+fn deploy_superdao_closing_at(session: &mut Session<Pop>, voting_period_end: u32) -> AccountId {
+	let mock = ContractMock::new()
+		.with_message(
+			ink::selector_bytes!("SuperDao::register_member"),
+			mock_message(|()| Ok::<(), superdao_traits::Error>(())),
+		)
+		.with_message(
+			ink::selector_bytes!("SuperDao::deregister_member"),
+			mock_message(|()| Ok::<(), superdao_traits::Error>(())),
+		)
+		.with_message(
+			ink::selector_bytes!("SuperDao::create_proposal"),
+			mock_message(|_: superdao_traits::Call| {
+				Ok::<u32, superdao_traits::Error>(SUPERDAO_PROPOSAL)
+			}),
+		)
+		.with_message(
+			ink::selector_bytes!("SuperDao::vote"),
+			mock_message(|_: (u32, superdao_traits::Vote)| Ok::<(), superdao_traits::Error>(())),
+		)
+		.with_message(
+			ink::selector_bytes!("SuperDao::get_proposal"),
+			mock_message(move |_: u32| {
+				Some(superdao_traits::Proposal {
+					call: superdao_traits::Call::Contract(superdao_traits::ContractCall {
+						callee: ink::primitives::AccountId::from([0; 32]),
+						selector: [0; 4],
+						input: vec![],
+						transferred_value: 0,
+						ref_time_limit: 0,
+						allow_reentry: false,
+					}),
+					voting_period_end,
+					status: superdao_traits::ProposalStatus::Approved,
+				})
+			}),
+		);
+	session.mocking_api().deploy(mock)
+}
+
+// Deploy the DAO taking part in `superdao`, voting with a new asset with ID `TOKEN`.
+fn deploy_with_default(
+	session: &mut Session<Pop>,
+	superdao: AccountId,
+) -> Result<AccountId, DaoError> {
+	deploy_with_strategy(session, superdao, "Linear")
+}
+
+// Deploy the DAO taking part in `superdao`, voting with a new asset with ID `TOKEN` weighted by
+// `strategy`.
+fn deploy_with_strategy(
+	session: &mut Session<Pop>,
+	superdao: AccountId,
+	strategy: &str,
+) -> Result<AccountId, DaoError> {
+	deploy(
+		session,
+		"new_with_asset",
+		vec![
+			"\"Test DAO\"".to_string(),
+			superdao.to_string(),
+			format!(
+				"NewAsset {{ id: {TOKEN}, min_balance: {MIN_BALANCE}, name: \"Vote\", symbol: \"VOTE\", decimals: 10 }}"
+			),
+			config(),
+			strategy.to_string(),
+			false.to_string(),
+		],
+	)
+}
+
+// Default governance parameters, voting on proposals and prevotes for `VOTING_PERIOD` blocks.
+fn config() -> String {
+	format!(
+		"GovernanceConfig {{ voting_delay: 0, voting_period: {VOTING_PERIOD}, quorum: 1, \
+		 approval_threshold: 50, execution_period: 100, registration: Open, \
+		 application_period: 100, registration_deposit: 0, mint_amount: {MINT_AMOUNT}, proposal_deposit: 0, \
+		 open_proposals: true }}"
+	)
+}
+
+// Deploy the DAO taking part in `superdao`, voting with a `Fungible` contract creating the asset
+// with ID `TOKEN`, and hand the ownership of the contract to the DAO. Returns the DAO and the
+// token contract.
+fn deploy_with_token(session: &mut Session<Pop>, superdao: AccountId) -> (AccountId, AccountId) {
+	let token = drink::deploy::<Pop, Psp22Error>(
+		session,
+		// The token contract (i.e. `fungibles`).
+		BundleProvider::Fungibles.bundle().unwrap(),
+		"new",
+		vec![TOKEN.to_string(), MIN_BALANCE.to_string()],
+		NO_SALT,
+		Some(INIT_VALUE),
+	)
+	.unwrap();
+	let dao = deploy(
+		session,
+		"new_with_token",
+		vec![
+			"\"Test DAO\"".to_string(),
+			superdao.to_string(),
+			token.to_string(),
+			config(),
+			"Linear".to_string(),
+			false.to_string(),
+		],
+	)
+	.unwrap();
+	assert_ok!(session
+		.call_with_address::<String, Result<(), Psp22Error>>(
+			token.clone(),
+			"transfer_ownership",
+			&[dao.to_string()],
+			None,
+		)
+		.unwrap()
+		.unwrap());
+	(dao, token)
+}
+
+// Deploy the contract with `NO_SALT and `INIT_VALUE`.
+fn deploy(
+	session: &mut Session<Pop>,
+	method: &str,
+	input: Vec<String>,
+) -> Result<AccountId, DaoError> {
+	drink::deploy::<Pop, DaoError>(
+		session,
+		// The local contract (i.e. `challenge-7-contract`).
+		BundleProvider::local().unwrap(),
+		method,
+		input,
+		NO_SALT,
+		Some(INIT_VALUE),
+	)
+}
+
+// Start the proposal, approve it by the vote of the actor, and finalize it.
+fn pass_proposal(session: &mut Session<Pop>, proposal_id: u32) {
+	assert_ok!(call::<Pop, (), DaoError>(
+		session,
+		"start_proposal",
+		vec![proposal_id.to_string()],
+		None
+	));
+	assert_ok!(vote(session, proposal_id, Ballot::Aye));
+	session.sandbox().build_blocks(VOTING_PERIOD);
+	assert_eq!(
+		call::<Pop, Result<ProposalStatus, DaoError>, DaoError>(
+			session,
+			"finalize_proposal",
+			vec![proposal_id.to_string()],
+			None
+		)
+		.and_then(|result| result),
+		Ok(ProposalStatus::Succeeded)
+	);
+}
+
+// A set of helper methods to test the contract calls. Messages returning a value or an error
+// decode as `Result<T, DaoError>` when they succeed, hence the `and_then`.
+
+fn register_voter(session: &mut Session<Pop>) -> Result<(), DaoError> {
+	call::<Pop, (), DaoError>(session, "register_voter", vec![], None)
+}
+
+// Register each of `voters`, leaving the actor as the first of them.
+fn register_voters(session: &mut Session<Pop>, voters: &[AccountId]) {
+	for voter in voters.iter().rev() {
+		session.set_actor(voter.clone());
+		assert_ok!(register_voter(session));
+	}
+}
+
+fn propose_action(session: &mut Session<Pop>, action: &str) -> Result<u32, DaoError> {
+	call::<Pop, Result<u32, DaoError>, DaoError>(
+		session,
+		"propose_action",
+		vec![action.to_string()],
+		None,
+	)
+	.and_then(|result| result)
+}
+
+// Propose to join or leave the Super DAO through `message`.
+fn propose_superdao_membership(session: &mut Session<Pop>, message: &str) -> Result<u32, DaoError> {
+	call::<Pop, Result<u32, DaoError>, DaoError>(session, message, vec![], None)
+		.and_then(|result| result)
+}
+
+fn vote(session: &mut Session<Pop>, proposal_id: u32, ballot: Ballot) -> Result<(), DaoError> {
+	call::<Pop, (), DaoError>(
+		session,
+		"vote",
+		vec![proposal_id.to_string(), format!("{ballot:?}")],
+		None,
+	)
+}
+
+fn execute(session: &mut Session<Pop>, proposal_id: u32) -> Result<(), DaoError> {
+	call::<Pop, (), DaoError>(session, "execute", vec![proposal_id.to_string()], None)
+}
+
+fn proposal_status(session: &mut Session<Pop>, proposal_id: u32) -> ProposalStatus {
+	call::<Pop, Option<minidao::dao::BasicProposal>, DaoError>(
+		session,
+		"get_proposal",
+		vec![proposal_id.to_string()],
+		None,
+	)
+	.unwrap()
+	.unwrap()
+	.status
+}
+
+fn is_superdao_member(session: &mut Session<Pop>) -> bool {
+	call::<Pop, bool, DaoError>(session, "is_superdao_member", vec![], None).unwrap()
+}
+
+fn get_superdao(session: &mut Session<Pop>) -> AccountId {
+	call::<Pop, AccountId, DaoError>(session, "get_superdao", vec![], None).unwrap()
+}
+
+fn create_xcm_proposal(session: &mut Session<Pop>) -> Result<u32, DaoError> {
+	call::<Pop, Result<u32, DaoError>, DaoError>(
+		session,
+		"create_xcm_proposal",
+		vec![
+			"XcmLocation { parents: 1, junctions: [Parachain(1000)] }".to_string(),
+			"XcmAsset { id: XcmLocation { parents: 1, junctions: [] }, amount: 1000000000 }"
+				.to_string(),
+			"Unlimited".to_string(),
+			"[Transact { origin_kind: SovereignAccount, ref_time: 1000000000, proof_size: 100000, \
+			 call: [0, 1] }]"
+				.to_string(),
+			// Unused fees go back to the sovereign account of the DAO chain.
+			"XcmLocation { parents: 1, junctions: [Parachain(4001)] }".to_string(),
+		],
+		None,
+	)
+	.and_then(|result| result)
+}
+
+fn get_cross_chain_proposal(
+	session: &mut Session<Pop>,
+	proposal_id: u32,
+) -> Result<minidao::dao::CrossChainProposal, DaoError> {
+	call::<Pop, Result<minidao::dao::CrossChainProposal, DaoError>, DaoError>(
+		session,
+		"get_cross_chain_proposal",
+		vec![proposal_id.to_string()],
+		None,
+	)
+	.and_then(|result| result)
+}
+
+fn sync_proposal(session: &mut Session<Pop>, proposal_id: u32) -> Result<ProposalStatus, DaoError> {
+	call::<Pop, Result<ProposalStatus, DaoError>, DaoError>(
+		session,
+		"sync_proposal",
+		vec![proposal_id.to_string()],
+		None,
+	)
+	.and_then(|result| result)
+}
+
+// Create a Super DAO proposal calling Bob, opening the prevote of `SUPERDAO_PROPOSAL`.
+fn create_prevote(session: &mut Session<Pop>) -> Result<u32, DaoError> {
+	call::<Pop, Result<u32, DaoError>, DaoError>(
+		session,
+		"create_contract_call_proposal",
+		vec![
+			BOB.to_string(),
+			"[0, 0, 0, 0]".to_string(),
+			"[]".to_string(),
+			0.to_string(),
+			0.to_string(),
+			false.to_string(),
+		],
+		None,
+	)
+	.and_then(|result| result)
+}
+
+fn submit_prevote(
+	session: &mut Session<Pop>,
+	approved: bool,
+	amount: Balance,
+	conviction: u8,
+) -> Result<(), DaoError> {
+	call::<Pop, (), DaoError>(
+		session,
+		"submit_prevote",
+		vec![
+			SUPERDAO_PROPOSAL.to_string(),
+			approved.to_string(),
+			amount.to_string(),
+			conviction.to_string(),
+		],
+		None,
+	)
+}
+
+fn get_prevote(session: &mut Session<Pop>) -> minidao::dao::Prevote {
+	call::<Pop, Option<minidao::dao::Prevote>, DaoError>(
+		session,
+		"get_prevote",
+		vec![SUPERDAO_PROPOSAL.to_string()],
+		None,
+	)
+	.unwrap()
+	.unwrap()
+}
+
+fn locked_balance(session: &mut Session<Pop>, voter: AccountId) -> Balance {
+	call::<Pop, Balance, DaoError>(
+		session,
+		"locked_balance",
+		vec![voter.to_string(), SUPERDAO_PROPOSAL.to_string()],
+		None,
+	)
+	.unwrap()
+}
+
+fn withdraw_locked(session: &mut Session<Pop>) -> Result<(), DaoError> {
+	call::<Pop, (), DaoError>(session, "withdraw_locked", vec![SUPERDAO_PROPOSAL.to_string()], None)
+}
+
+fn withdraw_locked_from(session: &mut Session<Pop>, superdao: AccountId) -> Result<(), DaoError> {
+	call::<Pop, (), DaoError>(
+		session,
+		"withdraw_locked_from",
+		vec![superdao.to_string(), SUPERDAO_PROPOSAL.to_string()],
+		None,
+	)
+}
+
+fn voting_power(session: &mut Session<Pop>, account: AccountId) -> Result<Balance, DaoError> {
+	call::<Pop, Result<Balance, DaoError>, DaoError>(
+		session,
+		"voting_power",
+		vec![account.to_string()],
+		None,
+	)
+	.and_then(|result| result)
+}
+
+fn undelegate(session: &mut Session<Pop>) -> Result<(), DaoError> {
+	call::<Pop, (), DaoError>(session, "undelegate", vec![], None)
+}
+
+fn prevote_voters(session: &mut Session<Pop>, offset: u32, limit: u32) -> Vec<AccountId> {
+	call::<Pop, Vec<AccountId>, DaoError>(
+		session,
+		"prevote_voters",
+		vec![SUPERDAO_PROPOSAL.to_string(), offset.to_string(), limit.to_string()],
+		None,
+	)
+	.unwrap()
+}
+
+fn finalize_prevote(session: &mut Session<Pop>) -> Result<Ballot, DaoError> {
+	call::<Pop, Result<Ballot, DaoError>, DaoError>(
+		session,
+		"finalize_prevote",
+		vec![SUPERDAO_PROPOSAL.to_string()],
+		None,
+	)
+	.and_then(|result| result)
+}
+
+fn prevote_result(session: &mut Session<Pop>) -> Option<Ballot> {
+	call::<Pop, Option<Ballot>, DaoError>(
+		session,
+		"prevote_result",
+		vec![SUPERDAO_PROPOSAL.to_string()],
+		None,
+	)
+	.unwrap()
+}
@@ -0,0 +1,127 @@
+use ink::{
+    env::{
+        call::{build_call, ExecutionInput, Selector},
+        CallFlags, DefaultEnvironment,
+    },
+    prelude::vec::Vec,
+};
+
+use crate::{AccountId, Balance, DaoError};
+
+/// Cross-contract call made when a proposal is executed.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct ProposalCall {
+    /// Contract to call.
+    pub callee: AccountId,
+    /// Selector of the message to call.
+    pub selector: [u8; 4],
+    /// SCALE-encoded arguments of the message.
+    pub input: Vec<u8>,
+    /// Value transferred along with the call.
+    pub transferred_value: Balance,
+    /// Maximum reference time the call may consume, `0` for no limit.
+    pub ref_time_limit: u64,
+    /// Whether the callee may call back into the DAO. Local proposals can't allow it, as the DAO
+    /// would write its state back over the changes made by the callee.
+    pub allow_reentry: bool,
+}
+
+/// Reason a cross-contract call failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub enum CallError {
+    // Callee trapped.
+    CalleeTrapped,
+    // Callee reverted.
+    CalleeReverted,
+    // Value could not be transferred.
+    TransferFailed,
+    // Callee is not a contract.
+    NotCallable,
+    // Callee could not dispatch the message.
+    CouldNotDispatch,
+    // Call failed for another reason.
+    Other,
+}
+
+/// Result of a proposal call: the SCALE-encoded return value of the message, or why it failed.
+pub type CallOutcome = Result<Vec<u8>, CallError>;
+
+impl ProposalCall {
+    /// Check that the call has a callee.
+    pub fn validate(&self) -> Result<(), DaoError> {
+        if self.callee == AccountId::from([0; 32]) {
+            return Err(DaoError::InvalidCall);
+        }
+        Ok(())
+    }
+
+    /// Perform the call from the current contract.
+    pub fn invoke(&self) -> CallOutcome {
+        let output = build_call::<DefaultEnvironment>()
+            .call(self.callee)
+            .ref_time_limit(self.ref_time_limit)
+            .transferred_value(self.transferred_value)
+            .call_flags(CallFlags::default().set_allow_reentry(self.allow_reentry))
+            .exec_input(
+                ExecutionInput::new(Selector::new(self.selector)).push_arg(RawInput(&self.input)),
+            )
+            .returns::<RawOutput>()
+            .try_invoke();
+        flatten_call(output).map(|output| output.0)
+    }
+}
+
+/// Returns the value returned by a cross-contract call, or why the call could not be made.
+pub fn flatten_call<T>(
+    result: Result<ink::MessageResult<T>, ink::env::Error>,
+) -> Result<T, CallError> {
+    Ok(result??)
+}
+
+impl From<ink::env::Error> for CallError {
+    fn from(error: ink::env::Error) -> Self {
+        match error {
+            ink::env::Error::CalleeTrapped => CallError::CalleeTrapped,
+            ink::env::Error::CalleeReverted => CallError::CalleeReverted,
+            ink::env::Error::TransferFailed => CallError::TransferFailed,
+            ink::env::Error::NotCallable => CallError::NotCallable,
+            _ => CallError::Other,
+        }
+    }
+}
+
+impl From<ink::LangError> for CallError {
+    fn from(_: ink::LangError) -> Self {
+        CallError::CouldNotDispatch
+    }
+}
+
+/// Arguments which are already encoded.
+struct RawInput<'a>(&'a [u8]);
+
+impl scale::Encode for RawInput<'_> {
+    fn encode_to<T: scale::Output + ?Sized>(&self, dest: &mut T) {
+        dest.write(self.0);
+    }
+}
+
+/// Return value kept encoded, whatever its type.
+struct RawOutput(Vec<u8>);
+
+impl scale::Decode for RawOutput {
+    fn decode<I: scale::Input>(input: &mut I) -> Result<Self, scale::Error> {
+        let mut output = Vec::new();
+        while let Ok(byte) = input.read_byte() {
+            output.push(byte);
+        }
+        Ok(Self(output))
+    }
+}
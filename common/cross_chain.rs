@@ -0,0 +1,93 @@
+use ink::prelude::vec::Vec;
+
+use crate::Balance;
+
+/// Location of a consensus system, relative to the chain interpreting it.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct XcmLocation {
+    /// Number of levels up from the interpreting chain.
+    pub parents: u8,
+    /// Path down from there, at most eight junctions.
+    pub junctions: Vec<XcmJunction>,
+}
+
+/// Step of the path of an [`XcmLocation`].
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum XcmJunction {
+    // Parachain with the given ID.
+    Parachain(u32),
+    // 32-byte account.
+    AccountId32([u8; 32]),
+    // 20-byte account.
+    AccountKey20([u8; 20]),
+    // Pallet with the given index.
+    PalletInstance(u8),
+    // Item with the given index, e.g. an asset ID.
+    GeneralIndex(u128),
+}
+
+/// Amount of a fungible asset.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct XcmAsset {
+    /// Location identifying the asset.
+    pub id: XcmLocation,
+    /// Amount of the asset.
+    pub amount: Balance,
+}
+
+/// Maximum weight bought for the execution of a message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum XcmWeightLimit {
+    // As much weight as the fee pays for.
+    Unlimited,
+    // At most the given weight.
+    Limited { ref_time: u64, proof_size: u64 },
+}
+
+/// Origin a remote call is dispatched with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum XcmOriginKind {
+    // Native origin of the sending chain.
+    Native,
+    // Sovereign account of the sending chain.
+    SovereignAccount,
+    // Root origin.
+    Superuser,
+    // XCM pallet origin.
+    Xcm,
+}
+
+/// Common pattern of an XCM message proposed to the Super DAO.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum XcmInstruction {
+    /// Dispatch the SCALE-encoded runtime `call` on the destination.
+    Transact {
+        origin_kind: XcmOriginKind,
+        ref_time: u64,
+        proof_size: u64,
+        call: Vec<u8>,
+    },
+    /// Withdraw `asset` from its `reserve` and deposit it to `beneficiary`. The `remote_fee`
+    /// pays for the execution on the reserve, and is expressed as seen from it, as is
+    /// `beneficiary`.
+    ReserveTransfer {
+        asset: XcmAsset,
+        reserve: XcmLocation,
+        remote_fee: XcmAsset,
+        beneficiary: XcmLocation,
+    },
+    /// Teleport `asset` to `dest` and deposit it to `beneficiary`. The `remote_fee` pays for the
+    /// execution on `dest`, and is expressed as seen from it, as is `beneficiary`.
+    Teleport {
+        asset: XcmAsset,
+        dest: XcmLocation,
+        remote_fee: XcmAsset,
+        beneficiary: XcmLocation,
+    },
+}
@@ -0,0 +1,155 @@
+use ink::prelude::string::String;
+
+use crate::CallError;
+
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum DaoError {
+    // Membership
+
+    // Voter is already registered in the Dao.
+    VoterAlreadyRegistered,
+    // Voter is not registered yet.
+    VoterNotRegistered,
+    // Registration is not by application.
+    ApplicationsClosed,
+    // Account has already applied.
+    ApplicationPending,
+    // Account has not applied.
+    ApplicationDoesNotExist,
+    // Application is past its expiry.
+    ApplicationExpired,
+    // Member has already approved or rejected the application.
+    ApplicationAlreadyReviewed,
+
+    // Authorization
+
+    // Caller is not the proposer of the proposal.
+    NotProposer,
+    // Caller lacks the role required.
+    MissingRole,
+    // Account already has the role.
+    RoleAlreadyGranted,
+    // Account does not have the role.
+    RoleNotGranted,
+
+    // Deposits
+
+    // Value transferred is not the deposit required.
+    IncorrectDeposit,
+    // Deposit of the account is lower than the amount.
+    InsufficientDeposit,
+    // Deposit could not be refunded.
+    RefundFailed,
+    // Deposit is frozen while a proposal slashing it is pending.
+    DepositFrozen,
+    // Balance of the DAO does not cover the deposits it holds and the value to transfer.
+    InsufficientFunds,
+
+    // Governance parameters
+
+    // Governance parameters are out of range.
+    InvalidGovernanceConfig,
+
+    // Proposal lifecycle
+
+    // Proposal does not exist in the Dao.
+    ProposalDoesNotExist,
+    // Proposal is not a draft anymore.
+    ProposalNotDraft,
+    // Proposal is not open for voting.
+    ProposalNotActive,
+    // Voting of the proposal has not started yet.
+    VotingNotStarted,
+    // Voting period of the proposal is over.
+    VotingPeriodEnded,
+    // Voting period of the proposal is not over yet.
+    VotingPeriodNotEnded,
+    // Proposal did not succeed.
+    ProposalNotSucceeded,
+    // Execution period of the proposal is not over yet.
+    ExecutionPeriodNotEnded,
+    // Execution period of the proposal is over.
+    ExecutionPeriodEnded,
+    // Proposal has already been finalized.
+    ProposalAlreadyFinalized,
+    // Call of the proposal is not valid.
+    InvalidCall,
+
+    // Votes
+
+    // Voter has already voted the proposal.
+    AlreadyVoted,
+    // Voter has not voted the proposal.
+    NotVoted,
+    // Conviction is higher than the voting strategy accepts.
+    InvalidConviction,
+    // Tokens of the vote are still locked.
+    TokensLocked,
+    // Voter held fewer tokens than the amount at the snapshot of the prevote.
+    ExceedsSnapshotBalance,
+
+    // Delegation
+
+    // Account can't delegate to itself.
+    SelfDelegation,
+    // Delegate has delegated, or delegator has delegates: delegations can't be chained.
+    DelegationChain,
+    // Account has already delegated.
+    AlreadyDelegated,
+    // Account has not delegated.
+    NotDelegated,
+    // Account has no tokens to delegate.
+    NothingToDelegate,
+    // Delegate counts the delegated tokens in a prevote still open.
+    DelegationInUse,
+
+    // Token
+
+    // Call to the token contract could not be made.
+    TokenCallFailed(CallError),
+    // Token contract refused the operation.
+    Token(TokenError),
+
+    // Super DAO
+
+    // Call to the Super DAO contract could not be made.
+    SuperDaoCallFailed(CallError),
+    // Super DAO refused the operation for another reason than the ones below.
+    SuperDaoRejected,
+    // DAO is already a member of the Super DAO.
+    AlreadySuperDaoMember,
+    // DAO is not a member of the Super DAO.
+    NotSuperDaoMember,
+    // Super DAO address is zero or the current one.
+    InvalidSuperDaoAddress,
+
+    // XCM
+
+    // Cross-chain message of the proposal is not valid.
+    InvalidCrossChainMessage,
+}
+
+/// Error of a PSP22 token operation.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum TokenError {
+    // Error specific to the token implementation.
+    Custom(String),
+    // Not enough balance to fulfill the operation.
+    InsufficientBalance,
+    // Not enough allowance to fulfill the operation.
+    InsufficientAllowance,
+    // Recipient is the zero address.
+    ZeroRecipientAddress,
+    // Sender is the zero address.
+    ZeroSenderAddress,
+    // Recipient contract refused the transfer.
+    SafeTransferCheckFailed(String),
+}
+
+impl From<TokenError> for DaoError {
+    fn from(error: TokenError) -> Self {
+        DaoError::Token(error)
+    }
+}
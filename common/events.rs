@@ -0,0 +1,163 @@
+use crate::{
+    AccountId, Balance, Ballot, BlockNumber, GovernanceConfig, ProposalStatus, Role, Strategy,
+};
+
+/// A voter registered in the DAO.
+#[ink::event]
+pub struct VoterRegistered {
+    #[ink(topic)]
+    pub voter: AccountId,
+}
+
+/// A voter left the DAO.
+#[ink::event]
+pub struct VoterDeregistered {
+    #[ink(topic)]
+    pub voter: AccountId,
+}
+
+/// An account applied to become a voter.
+#[ink::event]
+pub struct MembershipApplied {
+    #[ink(topic)]
+    pub applicant: AccountId,
+    pub expires_at: BlockNumber,
+}
+
+/// A member or an admin approved or rejected an application.
+#[ink::event]
+pub struct ApplicationReviewed {
+    #[ink(topic)]
+    pub applicant: AccountId,
+    #[ink(topic)]
+    pub reviewer: AccountId,
+    pub approved: bool,
+}
+
+/// An application was closed, registering the applicant if `approved`.
+#[ink::event]
+pub struct ApplicationClosed {
+    #[ink(topic)]
+    pub applicant: AccountId,
+    pub approved: bool,
+}
+
+/// Governance slashed the deposit of a voter.
+#[ink::event]
+pub struct DepositSlashed {
+    #[ink(topic)]
+    pub voter: AccountId,
+    pub amount: Balance,
+}
+
+/// A proposal was created, locally or on the Super DAO.
+#[ink::event]
+pub struct ProposalCreated {
+    #[ink(topic)]
+    pub proposal_id: u32,
+    #[ink(topic)]
+    pub proposer: AccountId,
+    /// Whether `proposal_id` is the ID of a Super DAO proposal
+    pub superdao: bool,
+}
+
+/// A voter cast, changed or retracted (`None`) their ballot on a local proposal, or on the
+/// internal vote on a Super DAO proposal.
+#[ink::event]
+pub struct VoteCast {
+    #[ink(topic)]
+    pub proposal_id: u32,
+    #[ink(topic)]
+    pub voter: AccountId,
+    pub ballot: Option<Ballot>,
+    /// Whether `proposal_id` is the ID of a Super DAO proposal
+    pub superdao: bool,
+}
+
+/// A voter submitted, changed or retracted (`None`) their prevote on a Super DAO proposal.
+#[ink::event]
+pub struct PrevoteSubmitted {
+    #[ink(topic)]
+    pub proposal_id: u32,
+    #[ink(topic)]
+    pub voter: AccountId,
+    pub approved: Option<bool>,
+    pub weight: Balance,
+}
+
+/// A voter withdrew the tokens locked by their prevote.
+#[ink::event]
+pub struct TokensUnlocked {
+    #[ink(topic)]
+    pub proposal_id: u32,
+    #[ink(topic)]
+    pub voter: AccountId,
+    pub amount: Balance,
+}
+
+/// A voter delegated their tokens, or took them back (`None`).
+#[ink::event]
+pub struct DelegationChanged {
+    #[ink(topic)]
+    pub delegator: AccountId,
+    #[ink(topic)]
+    pub delegate: Option<AccountId>,
+    pub amount: Balance,
+}
+
+/// Governance changed the strategy weighting the prevotes opened from now on.
+#[ink::event]
+pub struct VotingStrategyChanged {
+    pub strategy: Strategy,
+}
+
+/// Governance updated its parameters.
+#[ink::event]
+pub struct ConfigUpdated {
+    pub config: GovernanceConfig,
+}
+
+/// A local proposal reached a new final or executed status.
+#[ink::event]
+pub struct ProposalFinalized {
+    #[ink(topic)]
+    pub proposal_id: u32,
+    pub status: ProposalStatus,
+}
+
+/// The DAO decided its vote on a Super DAO proposal. Abstentions are not cast.
+#[ink::event]
+pub struct SuperDaoVoteCast {
+    #[ink(topic)]
+    pub proposal_id: u32,
+    pub vote: Ballot,
+}
+
+/// A role was granted to an account.
+#[ink::event]
+pub struct RoleGranted {
+    #[ink(topic)]
+    pub account: AccountId,
+    pub role: Role,
+    #[ink(topic)]
+    pub by: AccountId,
+}
+
+/// A role was revoked from an account.
+#[ink::event]
+pub struct RoleRevoked {
+    #[ink(topic)]
+    pub account: AccountId,
+    pub role: Role,
+    #[ink(topic)]
+    pub by: AccountId,
+}
+
+/// The DAO moved to a new Super DAO.
+#[ink::event]
+pub struct SuperDaoMigrated {
+    #[ink(topic)]
+    pub old: AccountId,
+    #[ink(topic)]
+    pub new: AccountId,
+}
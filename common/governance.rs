@@ -0,0 +1,173 @@
+use crate::{Balance, BlockNumber, DaoError};
+
+/// Lifecycle of a proposal voted on by the DAO members.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub enum ProposalStatus {
+    // Created, voting has not started yet.
+    Draft,
+    // Open for voting until the end of its voting period.
+    Active,
+    // Voting ended with the quorum and approval threshold reached.
+    Succeeded,
+    // Voting ended without reaching the quorum or approval threshold.
+    Defeated,
+    // Succeeded and executed.
+    Executed,
+    // Succeeded but not executed before the end of its execution period.
+    Expired,
+}
+
+/// Vote of a member on a proposal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub enum Ballot {
+    // In favor of the proposal.
+    Aye,
+    // Against the proposal.
+    Nay,
+    // Counts towards the quorum only.
+    Abstain,
+}
+
+/// Votes cast on a proposal, by ballot.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct Tally {
+    pub ayes: u32,
+    pub nays: u32,
+    pub abstains: u32,
+}
+
+impl Tally {
+    /// Count a ballot.
+    pub fn add(&mut self, ballot: Ballot) {
+        match ballot {
+            Ballot::Aye => self.ayes += 1,
+            Ballot::Nay => self.nays += 1,
+            Ballot::Abstain => self.abstains += 1,
+        }
+    }
+
+    /// Discount a ballot previously counted.
+    pub fn remove(&mut self, ballot: Ballot) {
+        match ballot {
+            Ballot::Aye => self.ayes -= 1,
+            Ballot::Nay => self.nays -= 1,
+            Ballot::Abstain => self.abstains -= 1,
+        }
+    }
+
+    /// Returns the number of votes cast.
+    pub fn total(&self) -> u32 {
+        self.ayes + self.nays + self.abstains
+    }
+}
+
+/// How accounts become voters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub enum Registration {
+    // Anyone registers.
+    Open,
+    // Accounts with the `Member` role register.
+    Gated,
+    // Accounts apply, and are registered once approved.
+    Application,
+}
+
+/// Parameters of the DAO governance, set when the DAO is instantiated and updated by its
+/// proposals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct GovernanceConfig {
+    /// Number of blocks between the start of a proposal, or prevote, and its voting.
+    pub voting_delay: BlockNumber,
+    /// Number of blocks a proposal is open for voting.
+    pub voting_period: BlockNumber,
+    /// Minimum number of votes for a proposal to be decided.
+    pub quorum: u32,
+    /// Percentage of the votes which must approve a proposal for it to succeed.
+    pub approval_threshold: u8,
+    /// Number of blocks a succeeded proposal can be executed for once voting ended.
+    pub execution_period: BlockNumber,
+    /// How accounts become voters.
+    pub registration: Registration,
+    /// Number of blocks a membership application stays open.
+    pub application_period: BlockNumber,
+    /// Deposit paid to register as a voter, held until deregistration.
+    pub registration_deposit: Balance,
+    /// Amount of tokens minted for each new voter, when voting with tokens.
+    pub mint_amount: Balance,
+    /// Deposit paid to create a proposal, refunded once it is decided.
+    pub proposal_deposit: Balance,
+    /// Whether any voter can create proposals, rather than `Proposer`s only.
+    pub open_proposals: bool,
+}
+
+impl Default for GovernanceConfig {
+    fn default() -> Self {
+        Self {
+            voting_delay: 0,
+            voting_period: 100,
+            quorum: 1,
+            approval_threshold: 50,
+            execution_period: 100,
+            registration: Registration::Open,
+            application_period: 100,
+            registration_deposit: 0,
+            mint_amount: 100,
+            proposal_deposit: 0,
+            open_proposals: true,
+        }
+    }
+}
+
+impl GovernanceConfig {
+    /// Check that the parameters describe a usable governance.
+    pub fn validate(&self) -> Result<(), DaoError> {
+        if self.voting_period == 0
+            || self.approval_threshold == 0
+            || self.approval_threshold > 100
+            || self.application_period == 0
+        {
+            return Err(DaoError::InvalidGovernanceConfig);
+        }
+        Ok(())
+    }
+
+    /// Returns whether the votes reach the quorum, and the ayes the approval threshold of the
+    /// ayes and nays. Abstentions only count towards the quorum.
+    pub fn is_approved(&self, tally: &Tally) -> bool {
+        let decisive = u64::from(tally.ayes) + u64::from(tally.nays);
+        tally.ayes > 0
+            && tally.total() >= self.quorum
+            && u64::from(tally.ayes) * 100 >= u64::from(self.approval_threshold) * decisive
+    }
+
+    /// Returns whether the weight of the ayes exceeds the approval threshold of the weight of
+    /// the ayes and nays, so that a tie is rejected under the default threshold. A threshold of
+    /// 100 requires every vote to approve.
+    pub fn is_approved_by_weight(&self, ayes: Balance, nays: Balance) -> bool {
+        ayes > 0
+            && (nays == 0
+                || ayes.saturating_mul(100)
+                    > Balance::from(self.approval_threshold)
+                        .saturating_mul(ayes.saturating_add(nays)))
+    }
+}
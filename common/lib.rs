@@ -2,25 +2,31 @@
 
 use ink::env::{DefaultEnvironment, Environment};
 
+mod call;
+mod cross_chain;
+mod error;
+mod events;
+mod governance;
+mod membership;
+mod roles;
+mod strategy;
+
+pub use call::{flatten_call, CallError, CallOutcome, ProposalCall};
+pub use cross_chain::{
+    XcmAsset, XcmInstruction, XcmJunction, XcmLocation, XcmOriginKind, XcmWeightLimit,
+};
+pub use error::{DaoError, TokenError};
+pub use events::{
+    ApplicationClosed, ApplicationReviewed, ConfigUpdated, DelegationChanged, DepositSlashed,
+    MembershipApplied, PrevoteSubmitted, ProposalCreated, ProposalFinalized, RoleGranted,
+    RoleRevoked, SuperDaoMigrated, SuperDaoVoteCast, TokensUnlocked, VoteCast, VoterDeregistered,
+    VoterRegistered, VotingStrategyChanged,
+};
+pub use governance::{Ballot, GovernanceConfig, ProposalStatus, Registration, Tally};
+pub use membership::Membership;
+pub use roles::{Role, Roles};
+pub use strategy::{Conviction, Linear, OneMemberOneVote, Quadratic, Strategy, VotingStrategy};
+
 pub type AccountId = <DefaultEnvironment as Environment>::AccountId;
 pub type Balance = <DefaultEnvironment as Environment>::Balance;
 pub type BlockNumber = <DefaultEnvironment as Environment>::BlockNumber;
-
-#[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
-#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
-pub enum DaoError {
-    // Voter is already registered in the Dao.
-    VoterAlreadyRegistered,
-    // Voter is not registered yet..
-    VoterNotRegistered,
-    // Voter already voted the proposal.
-    VoterAlreadyVoted,
-    // Proposal does not exist in the Dao.
-    ProposalDoesNotExist,
-    // Prevote period is not ended.
-    PrevotePeriodIsNotEnded,
-    // No contract address.
-    NoContractAddress,
-    // Voter has already voted the proposal.
-    AlreadyVoted,
-}
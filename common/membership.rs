@@ -0,0 +1,75 @@
+use ink::{
+    prelude::vec::Vec,
+    storage::{
+        traits::{AutoKey, StorageKey},
+        Mapping,
+    },
+};
+
+use crate::{AccountId, DaoError};
+
+/// Registry of the DAO members.
+///
+/// Members are kept in an enumerable list and their position in it is indexed by account, so
+/// registering, removing and looking up a member costs the same whatever the size of the DAO.
+/// `KEY` sets where it is stored, so that a contract can hold several registries.
+#[ink::storage_item]
+#[derive(Default)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct Membership<KEY: StorageKey = AutoKey> {
+    /// Position of each member in `members`.
+    index: Mapping<AccountId, u32>,
+    /// Members by position.
+    members: Mapping<u32, AccountId>,
+    /// Number of members.
+    count: u32,
+}
+
+impl<KEY: StorageKey> Membership<KEY> {
+    /// Returns whether `account` is a member.
+    pub fn contains(&self, account: AccountId) -> bool {
+        self.index.contains(account)
+    }
+
+    /// Returns the number of members.
+    pub fn count(&self) -> u32 {
+        self.count
+    }
+
+    /// Returns up to `limit` members, starting from position `offset`.
+    pub fn page(&self, offset: u32, limit: u32) -> Vec<AccountId> {
+        let end = offset.saturating_add(limit).min(self.count);
+        (offset..end).filter_map(|i| self.members.get(i)).collect()
+    }
+
+    /// Adds `account` at the end of the list.
+    pub fn add(&mut self, account: AccountId) -> Result<(), DaoError> {
+        if self.contains(account) {
+            return Err(DaoError::VoterAlreadyRegistered);
+        }
+        self.index.insert(account, &self.count);
+        self.members.insert(self.count, &account);
+        self.count += 1;
+        Ok(())
+    }
+
+    /// Removes `account`, moving the last member into its position.
+    pub fn remove(&mut self, account: AccountId) -> Result<(), DaoError> {
+        let position = self
+            .index
+            .take(account)
+            .ok_or(DaoError::VoterNotRegistered)?;
+        let last = self.count - 1;
+        if position != last {
+            let moved = self
+                .members
+                .get(last)
+                .expect("every position below `count` holds a member");
+            self.members.insert(position, &moved);
+            self.index.insert(moved, &position);
+        }
+        self.members.remove(last);
+        self.count = last;
+        Ok(())
+    }
+}
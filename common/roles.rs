@@ -0,0 +1,59 @@
+use ink::storage::Mapping;
+
+use crate::{AccountId, DaoError};
+
+/// Permission granted to an account in the DAO.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub enum Role {
+    // Grants and revokes roles.
+    Admin,
+    // Creates proposals when proposal creation is gated.
+    Proposer,
+    // Registers as a voter when registration is gated.
+    Member,
+    // Removes the draft proposals of any proposer.
+    Guardian,
+}
+
+/// Roles granted to the accounts of the DAO.
+#[ink::storage_item]
+#[derive(Default)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct Roles {
+    granted: Mapping<(AccountId, Role), ()>,
+}
+
+impl Roles {
+    /// Returns whether `account` has `role`.
+    pub fn has(&self, account: AccountId, role: Role) -> bool {
+        self.granted.contains((account, role))
+    }
+
+    /// Check that `account` has `role`.
+    pub fn ensure(&self, account: AccountId, role: Role) -> Result<(), DaoError> {
+        if !self.has(account, role) {
+            return Err(DaoError::MissingRole);
+        }
+        Ok(())
+    }
+
+    /// Grants `role` to `account`.
+    pub fn grant(&mut self, account: AccountId, role: Role) -> Result<(), DaoError> {
+        if self.has(account, role) {
+            return Err(DaoError::RoleAlreadyGranted);
+        }
+        self.granted.insert((account, role), &());
+        Ok(())
+    }
+
+    /// Revokes `role` from `account`.
+    pub fn revoke(&mut self, account: AccountId, role: Role) -> Result<(), DaoError> {
+        self.granted
+            .take((account, role))
+            .ok_or(DaoError::RoleNotGranted)
+    }
+}
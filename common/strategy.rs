@@ -0,0 +1,112 @@
+use crate::Balance;
+
+/// How the tokens backing a vote translate into its weight.
+pub trait VotingStrategy {
+    /// Returns the weight of a vote backed by `balance` tokens, locked for `conviction` more
+    /// voting periods once voting ends.
+    fn weight(&self, balance: Balance, conviction: u8) -> Balance;
+
+    /// Returns the weight of a vote backed by `balance` tokens of the voter, locked with
+    /// `conviction`, and `delegated` tokens of others. The voter can't lock the tokens of others,
+    /// so they count without conviction.
+    fn weight_with_delegations(
+        &self,
+        balance: Balance,
+        delegated: Balance,
+        conviction: u8,
+    ) -> Balance {
+        let own = self.weight(balance, 0);
+        let with_delegations = self.weight(balance.saturating_add(delegated), 0);
+        self.weight(balance, conviction)
+            .saturating_add(with_delegations.saturating_sub(own))
+    }
+
+    /// Returns the highest conviction accepted.
+    fn max_conviction(&self) -> u8 {
+        0
+    }
+}
+
+/// One vote per token.
+pub struct Linear;
+
+impl VotingStrategy for Linear {
+    fn weight(&self, balance: Balance, _conviction: u8) -> Balance {
+        balance
+    }
+}
+
+/// The square root of the tokens, so that large holders weigh less.
+pub struct Quadratic;
+
+impl VotingStrategy for Quadratic {
+    fn weight(&self, balance: Balance, _conviction: u8) -> Balance {
+        balance.isqrt()
+    }
+}
+
+/// One vote per member, whatever their tokens.
+pub struct OneMemberOneVote;
+
+impl VotingStrategy for OneMemberOneVote {
+    fn weight(&self, _balance: Balance, _conviction: u8) -> Balance {
+        1
+    }
+}
+
+/// One vote per token, multiplied by one plus the conviction.
+pub struct Conviction;
+
+impl Conviction {
+    /// Highest conviction, multiplying the weight by seven.
+    pub const MAX: u8 = 6;
+}
+
+impl VotingStrategy for Conviction {
+    fn weight(&self, balance: Balance, conviction: u8) -> Balance {
+        balance.saturating_mul(Balance::from(conviction) + 1)
+    }
+
+    fn max_conviction(&self) -> u8 {
+        Self::MAX
+    }
+}
+
+/// Voting strategy chosen by a DAO.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub enum Strategy {
+    // One vote per token.
+    #[default]
+    Linear,
+    // Square root of the tokens.
+    Quadratic,
+    // One vote per member.
+    OneMemberOneVote,
+    // One vote per token, multiplied by the conviction.
+    Conviction,
+}
+
+impl Strategy {
+    fn get(&self) -> &dyn VotingStrategy {
+        match self {
+            Strategy::Linear => &Linear,
+            Strategy::Quadratic => &Quadratic,
+            Strategy::OneMemberOneVote => &OneMemberOneVote,
+            Strategy::Conviction => &Conviction,
+        }
+    }
+}
+
+impl VotingStrategy for Strategy {
+    fn weight(&self, balance: Balance, conviction: u8) -> Balance {
+        self.get().weight(balance, conviction)
+    }
+
+    fn max_conviction(&self) -> u8 {
+        self.get().max_conviction()
+    }
+}
@@ -0,0 +1,133 @@
+use ink::prelude::{vec, vec::Vec};
+use minidao_common::*;
+use xcm::{v3::prelude::*, VersionedXcm};
+
+/// Build the message executed on the destination of a cross-chain proposal: `fee` is withdrawn
+/// to buy the execution of `instructions`, and what is left of it is refunded and deposited to
+/// `refund_to`.
+pub fn build_message(
+    fee: XcmAsset,
+    weight_limit: XcmWeightLimit,
+    instructions: Vec<XcmInstruction>,
+    refund_to: XcmLocation,
+) -> Result<VersionedXcm<()>, DaoError> {
+    if instructions.is_empty() {
+        return Err(DaoError::InvalidCrossChainMessage);
+    }
+
+    let fee = asset(fee)?;
+    let mut message = vec![
+        WithdrawAsset(fee.clone().into()),
+        BuyExecution {
+            fees: fee,
+            weight_limit: match weight_limit {
+                XcmWeightLimit::Unlimited => Unlimited,
+                XcmWeightLimit::Limited {
+                    ref_time,
+                    proof_size,
+                } => Limited(Weight::from_parts(ref_time, proof_size)),
+            },
+        },
+    ];
+    for instruction in instructions {
+        match instruction {
+            XcmInstruction::Transact {
+                origin_kind,
+                ref_time,
+                proof_size,
+                call,
+            } => message.push(Transact {
+                origin_kind: match origin_kind {
+                    XcmOriginKind::Native => OriginKind::Native,
+                    XcmOriginKind::SovereignAccount => OriginKind::SovereignAccount,
+                    XcmOriginKind::Superuser => OriginKind::Superuser,
+                    XcmOriginKind::Xcm => OriginKind::Xcm,
+                },
+                require_weight_at_most: Weight::from_parts(ref_time, proof_size),
+                call: call.into(),
+            }),
+            XcmInstruction::ReserveTransfer {
+                asset: transferred,
+                reserve,
+                remote_fee,
+                beneficiary,
+            } => {
+                let transferred = asset(transferred)?;
+                message.push(WithdrawAsset(transferred.clone().into()));
+                message.push(InitiateReserveWithdraw {
+                    assets: Definite(transferred.into()),
+                    reserve: location(reserve)?,
+                    xcm: deposit(asset(remote_fee)?, location(beneficiary)?),
+                });
+            }
+            XcmInstruction::Teleport {
+                asset: transferred,
+                dest,
+                remote_fee,
+                beneficiary,
+            } => {
+                let transferred = asset(transferred)?;
+                message.push(WithdrawAsset(transferred.clone().into()));
+                message.push(InitiateTeleport {
+                    assets: Definite(transferred.into()),
+                    dest: location(dest)?,
+                    xcm: deposit(asset(remote_fee)?, location(beneficiary)?),
+                });
+            }
+        }
+    }
+    message.push(RefundSurplus);
+    message.push(DepositAsset {
+        assets: Wild(AllCounted(1)),
+        beneficiary: location(refund_to)?,
+    });
+    Ok(VersionedXcm::V3(Xcm(message)))
+}
+
+/// Returns the ID of the parachain at `location`, relative to a sibling parachain, or 0 for any
+/// other location.
+pub fn parachain_id(location: &XcmLocation) -> u32 {
+    match (location.parents, location.junctions.as_slice()) {
+        (1, [XcmJunction::Parachain(id)]) => *id,
+        _ => 0,
+    }
+}
+
+/// Convert a location, refusing more junctions than XCM supports.
+pub fn location(location: XcmLocation) -> Result<MultiLocation, DaoError> {
+    let mut interior = Junctions::Here;
+    for junction in location.junctions {
+        interior
+            .push(match junction {
+                XcmJunction::Parachain(id) => Parachain(id),
+                XcmJunction::AccountId32(id) => AccountId32 { network: None, id },
+                XcmJunction::AccountKey20(key) => AccountKey20 { network: None, key },
+                XcmJunction::PalletInstance(index) => PalletInstance(index),
+                XcmJunction::GeneralIndex(index) => GeneralIndex(index),
+            })
+            .map_err(|_| DaoError::InvalidCrossChainMessage)?;
+    }
+    Ok(MultiLocation::new(location.parents, interior))
+}
+
+fn asset(asset: XcmAsset) -> Result<MultiAsset, DaoError> {
+    Ok(MultiAsset {
+        id: Concrete(location(asset.id)?),
+        fun: Fungible(asset.amount),
+    })
+}
+
+/// Message paying for its execution with `fee` and depositing the received assets to
+/// `beneficiary`.
+fn deposit(fee: MultiAsset, beneficiary: MultiLocation) -> Xcm<()> {
+    Xcm(vec![
+        BuyExecution {
+            fees: fee,
+            weight_limit: Unlimited,
+        },
+        DepositAsset {
+            assets: Wild(AllCounted(1)),
+            beneficiary,
+        },
+    ])
+}
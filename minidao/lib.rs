@@ -0,0 +1,3495 @@
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+// # MiniDao
+//
+// The DAO contract behind every challenge. Each challenge crate is a thin configuration of this
+// contract, selecting the capabilities it needs through cargo features:
+//
+// - `proposals`: local proposals voted on by the DAO members (challenge 2).
+// - `superdao`: create and vote on proposals of the Super DAO (challenge 3).
+// - `xcm`: cross-chain proposals submitted to the Super DAO (challenge 4).
+// - `prevote`: token-backed prevotes deciding the DAO vote on the Super DAO (challenge 7).
+
+#[cfg(feature = "xcm")]
+mod cross_chain;
+
+#[ink::contract]
+pub mod dao {
+    #[cfg(feature = "prevote")]
+    use ink::env::{
+        call::{build_call, ExecutionInput, Selector},
+        DefaultEnvironment,
+    };
+    #[cfg(feature = "xcm")]
+    use ink::prelude::vec;
+    #[cfg(feature = "superdao")]
+    use ink::{codegen::TraitCallBuilder, contract_ref};
+    use ink::{
+        prelude::{string::String, vec::Vec},
+        storage::Mapping,
+    };
+    use minidao_common::*;
+    #[cfg(feature = "prevote")]
+    use pop_api::v0::fungibles::{
+        self as api,
+        traits::{Psp22, Psp22Mintable},
+        Psp22Error,
+    };
+    #[cfg(feature = "xcm")]
+    use superdao_traits::ChainCall;
+    #[cfg(feature = "superdao")]
+    use superdao_traits::{Call, ContractCall, SuperDao, Vote};
+    #[cfg(feature = "xcm")]
+    use xcm::v3::prelude::*;
+
+    #[derive(Clone, scale::Encode, scale::Decode)]
+    #[cfg_attr(
+        feature = "std",
+        derive(
+            Debug,
+            PartialEq,
+            Eq,
+            scale_info::TypeInfo,
+            ink::storage::traits::StorageLayout
+        )
+    )]
+    pub struct BasicProposal {
+        pub proposer: AccountId,
+        pub status: ProposalStatus,
+        pub voting_start: BlockNumber,
+        pub voting_end: BlockNumber,
+        pub tally: Tally,
+        /// Call made when the proposal is executed, if any
+        pub call: Option<ProposalCall>,
+        /// Outcome of the call, once executed
+        pub outcome: Option<CallOutcome>,
+        /// Deposit paid by the proposer, until refunded
+        pub deposit: Balance,
+        /// Change of the DAO applied when the proposal is executed, if any
+        #[cfg(feature = "proposals")]
+        pub action: Option<GovernanceAction>,
+    }
+
+    /// Change of the DAO itself, applied by `execute` rather than by a call of the DAO to its
+    /// own messages: ink! would write the state of `execute` back over the changes made by the
+    /// nested call.
+    #[derive(Clone, scale::Encode, scale::Decode)]
+    #[cfg_attr(
+        feature = "std",
+        derive(
+            Debug,
+            PartialEq,
+            Eq,
+            scale_info::TypeInfo,
+            ink::storage::traits::StorageLayout
+        )
+    )]
+    pub enum GovernanceAction {
+        /// Replace the governance parameters. Proposals and prevotes already started keep their
+        /// periods.
+        UpdateConfig(GovernanceConfig),
+        /// Slash `amount` from the deposit of `voter`, keeping it in the DAO
+        SlashDeposit { voter: AccountId, amount: Balance },
+        /// Register the DAO as a member of the Super DAO
+        #[cfg(feature = "superdao")]
+        JoinSuperDao,
+        /// Deregister the DAO from the Super DAO
+        #[cfg(feature = "superdao")]
+        LeaveSuperDao,
+        /// Move the DAO to another Super DAO, registering it there if it was a member of the
+        /// current one
+        #[cfg(feature = "superdao")]
+        MigrateSuperDao(AccountId),
+        /// Change the strategy weighting the prevotes opened from now on
+        #[cfg(feature = "prevote")]
+        SetVotingStrategy(Strategy),
+    }
+
+    /// Cross-chain proposal created on the Super DAO by this DAO.
+    #[derive(Clone, scale::Encode, scale::Decode)]
+    #[cfg_attr(
+        feature = "std",
+        derive(
+            Debug,
+            PartialEq,
+            Eq,
+            scale_info::TypeInfo,
+            ink::storage::traits::StorageLayout
+        )
+    )]
+    pub struct CrossChainProposal {
+        /// Super DAO the proposal was created on
+        pub superdao: AccountId,
+        /// ID of the proposal on the Super DAO
+        pub superdao_id: u32,
+        /// Status on the Super DAO, as of the last sync
+        pub status: ProposalStatus,
+        /// Parachain the message is sent to, or 0 for any other destination
+        pub chain_id: u32,
+        /// Call dispatched on the parachain, or the SCALE-encoded message for proposals built
+        /// from instructions
+        pub call_data: Vec<u8>,
+        pub votes_yes: u32,
+        pub votes_no: u32,
+    }
+
+    /// Internal vote of the members deciding the DAO vote on a Super DAO proposal.
+    #[derive(Clone, scale::Encode, scale::Decode)]
+    #[cfg_attr(
+        feature = "std",
+        derive(
+            Debug,
+            PartialEq,
+            Eq,
+            scale_info::TypeInfo,
+            ink::storage::traits::StorageLayout
+        )
+    )]
+    pub struct SuperDaoVote {
+        /// Block at which the internal vote ends
+        pub voting_end: BlockNumber,
+        pub tally: Tally,
+        /// Vote cast on the Super DAO, once finalized
+        pub outcome: Option<Ballot>,
+    }
+
+    #[derive(Clone, Default, scale::Encode, scale::Decode)]
+    #[cfg_attr(
+        feature = "std",
+        derive(
+            Debug,
+            PartialEq,
+            Eq,
+            scale_info::TypeInfo,
+            ink::storage::traits::StorageLayout
+        )
+    )]
+    /// Running totals of a prevote. The ballots and voters are stored apart, so that a vote
+    /// costs the same whatever the number of voters.
+    pub struct Prevote {
+        /// Block from which voters can prevote
+        pub voting_start: BlockNumber,
+        /// Block closing the prevote, before the Super DAO closes the proposal
+        pub deadline: BlockNumber,
+        /// Strategy weighting the votes, as of the creation of the prevote
+        pub strategy: Strategy,
+        /// Block before the creation of the prevote, whose balances cap the tokens a voter can
+        /// prevote with when the voting token records them
+        pub snapshot: BlockNumber,
+        /// Total weight of the approving votes
+        pub aye_weight: Balance,
+        /// Total weight of the disapproving votes
+        pub nay_weight: Balance,
+        /// Number of voters
+        pub voter_count: u32,
+        /// Vote cast on the Super DAO, once finalized
+        pub outcome: Option<Ballot>,
+    }
+
+    #[cfg(feature = "prevote")]
+    impl Prevote {
+        /// Count a vote of `weight`.
+        fn add(&mut self, ballot: Ballot, weight: Balance) {
+            match ballot {
+                Ballot::Aye => self.aye_weight += weight,
+                Ballot::Nay => self.nay_weight += weight,
+                Ballot::Abstain => {}
+            }
+        }
+
+        /// Discount a vote of `weight`.
+        fn remove(&mut self, ballot: Ballot, weight: Balance) {
+            match ballot {
+                Ballot::Aye => self.aye_weight -= weight,
+                Ballot::Nay => self.nay_weight -= weight,
+                Ballot::Abstain => {}
+            }
+        }
+    }
+
+    /// Token backing the prevotes.
+    #[derive(Clone, Copy, scale::Encode, scale::Decode)]
+    #[cfg_attr(
+        feature = "std",
+        derive(
+            Debug,
+            PartialEq,
+            Eq,
+            scale_info::TypeInfo,
+            ink::storage::traits::StorageLayout
+        )
+    )]
+    pub enum VotingToken {
+        // PSP22 contract owned by the DAO, such as `Fungible`, which moves tokens from its own
+        // account: it holds the escrowed tokens, and voters allow it to transfer their tokens.
+        Contract(AccountId),
+        // Pop fungible asset, by `TokenId`, created and administered by the DAO.
+        Asset(u32),
+    }
+
+    /// Pop fungible asset for the DAO to create on instantiation.
+    #[derive(Clone, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(Debug, PartialEq, Eq, scale_info::TypeInfo))]
+    pub struct NewAsset {
+        /// `TokenId` of the asset
+        pub id: u32,
+        /// Minimum balance of the accounts holding the asset
+        pub min_balance: Balance,
+        pub name: String,
+        pub symbol: String,
+        pub decimals: u8,
+    }
+
+    /// Tokens a voter escrowed to back a prevote.
+    #[derive(Clone, scale::Encode, scale::Decode)]
+    #[cfg_attr(
+        feature = "std",
+        derive(
+            Debug,
+            PartialEq,
+            Eq,
+            scale_info::TypeInfo,
+            ink::storage::traits::StorageLayout
+        )
+    )]
+    pub struct TokenLock {
+        pub amount: Balance,
+        /// Weight of the prevote, counting the tokens delegated to the voter
+        pub weight: Balance,
+        /// Block from which the tokens can be withdrawn
+        pub unlocks_at: BlockNumber,
+    }
+
+    /// Tokens a voter escrowed to add them to the prevotes of their delegate.
+    #[derive(Clone, scale::Encode, scale::Decode)]
+    #[cfg_attr(
+        feature = "std",
+        derive(
+            Debug,
+            PartialEq,
+            Eq,
+            scale_info::TypeInfo,
+            ink::storage::traits::StorageLayout
+        )
+    )]
+    pub struct Delegation {
+        pub delegate: AccountId,
+        pub amount: Balance,
+    }
+
+    /// Pending application of an account to become a voter.
+    #[derive(Clone, scale::Encode, scale::Decode)]
+    #[cfg_attr(
+        feature = "std",
+        derive(
+            Debug,
+            PartialEq,
+            Eq,
+            scale_info::TypeInfo,
+            ink::storage::traits::StorageLayout
+        )
+    )]
+    pub struct Application {
+        /// ID telling apart successive applications of the same account
+        pub id: u32,
+        /// Block after which the application can no longer be reviewed
+        pub expires_at: BlockNumber,
+        /// Number of members who approved the application
+        pub approvals: u32,
+        /// Number of members who rejected the application
+        pub rejections: u32,
+    }
+
+    /// Key of the state of a voter on a Super DAO proposal: the voter, the Super DAO and the ID
+    /// of the proposal on it.
+    pub type SuperDaoBallotKey = (AccountId, AccountId, u32);
+
+    /// Key of a position among the voters of a Super DAO proposal: the Super DAO, the ID of the
+    /// proposal on it and the position.
+    pub type SuperDaoVoterKey = (AccountId, u32, u32);
+
+    #[ink(storage)]
+    pub struct Dao {
+        /// Name of the DAO
+        name: String,
+        /// Registered voters
+        voters: Membership,
+        /// Governance parameters
+        config: GovernanceConfig,
+        /// Roles granted to accounts
+        roles: Roles,
+        /// Accounts with an application, expired or not
+        applicants: Membership,
+        /// Mapping of applications by applicant
+        applications: Mapping<AccountId, Application>,
+        /// Members who reviewed a specific application
+        application_reviews: Mapping<(AccountId, u32), ()>,
+        /// Counter for application IDs
+        next_application_id: u32,
+        /// Mapping of the deposit held for each voter
+        deposits: Mapping<AccountId, Balance>,
+        /// Sum of the deposits held
+        total_deposits: Balance,
+        /// Mapping of proposals
+        #[cfg(feature = "proposals")]
+        proposals: Mapping<u32, BasicProposal>,
+        /// Mapping of voter's vote count
+        #[cfg(feature = "proposals")]
+        vote_counts: Mapping<AccountId, u32>,
+        /// Mapping of the ballot cast by a voter on a specific proposal
+        #[cfg(feature = "proposals")]
+        ballots: Mapping<(AccountId, u32), Ballot>,
+        /// Counter for proposal IDs
+        #[cfg(feature = "proposals")]
+        next_proposal_id: u32,
+        /// Sum of the proposal deposits held
+        #[cfg(feature = "proposals")]
+        proposal_deposits: Balance,
+        /// Mapping of the number of pending proposals slashing the deposit of a voter
+        #[cfg(feature = "proposals")]
+        pending_slashes: Mapping<AccountId, u32>,
+        /// The Super DAO this DAO takes part in
+        #[cfg(feature = "superdao")]
+        superdao: AccountId,
+        /// Whether the DAO is registered as a member of the Super DAO
+        #[cfg(feature = "superdao")]
+        superdao_member: bool,
+        /// Mapping of internal votes by Super DAO and Super DAO proposal ID
+        #[cfg(all(feature = "superdao", not(feature = "prevote")))]
+        superdao_votes: Mapping<(AccountId, u32), SuperDaoVote>,
+        /// Mapping of the ballot cast by a voter on a specific Super DAO proposal
+        #[cfg(all(feature = "superdao", not(feature = "prevote")))]
+        superdao_ballots: Mapping<SuperDaoBallotKey, Ballot>,
+        /// Mapping of cross-chain proposals created by this DAO
+        #[cfg(feature = "xcm")]
+        cross_chain_proposals: Mapping<u32, CrossChainProposal>,
+        /// Mapping of Super DAO and Super DAO proposal IDs to local cross-chain proposal IDs
+        #[cfg(feature = "xcm")]
+        superdao_proposals: Mapping<(AccountId, u32), u32>,
+        /// Counter for cross-chain proposal IDs
+        #[cfg(feature = "xcm")]
+        next_cross_chain_proposal_id: u32,
+        /// The token backing the prevotes
+        #[cfg(feature = "prevote")]
+        token: VotingToken,
+        /// Accounts that were minted tokens on registration
+        #[cfg(feature = "prevote")]
+        minted: Mapping<AccountId, ()>,
+        /// Mapping of prevotes by Super DAO and Super DAO proposal ID
+        #[cfg(feature = "prevote")]
+        prevotes: Mapping<(AccountId, u32), Prevote>,
+        /// Mapping of the ballot cast by a voter on a specific prevote
+        #[cfg(feature = "prevote")]
+        prevote_ballots: Mapping<SuperDaoBallotKey, Ballot>,
+        /// Voters of each prevote, by position
+        #[cfg(feature = "prevote")]
+        prevote_voters: Mapping<SuperDaoVoterKey, AccountId>,
+        /// Position of each voter among the voters of a specific prevote
+        #[cfg(feature = "prevote")]
+        prevote_voter_positions: Mapping<SuperDaoBallotKey, u32>,
+        /// Strategy weighting the prevotes
+        #[cfg(feature = "prevote")]
+        strategy: Strategy,
+        /// Mapping of the tokens locked by a voter on a specific prevote
+        #[cfg(feature = "prevote")]
+        locked: Mapping<SuperDaoBallotKey, TokenLock>,
+        /// Mapping of delegations by delegator
+        #[cfg(feature = "prevote")]
+        delegations: Mapping<AccountId, Delegation>,
+        /// Mapping of the tokens delegated to each delegate
+        #[cfg(feature = "prevote")]
+        delegated: Mapping<AccountId, Balance>,
+        /// Mapping of the deadline of the last prevote counting the tokens delegated to each
+        /// delegate
+        #[cfg(feature = "prevote")]
+        delegation_in_use_until: Mapping<AccountId, BlockNumber>,
+    }
+
+    impl Dao {
+        // Constructor that initializes the values for the contract.
+        #[cfg(not(feature = "superdao"))]
+        #[ink(constructor)]
+        pub fn new(name: String, config: GovernanceConfig) -> Result<Self, DaoError> {
+            config.validate()?;
+            Ok(Self::init(name, config))
+        }
+
+        // Constructor that initializes the default values for the contract.
+        #[cfg(not(feature = "superdao"))]
+        #[ink(constructor)]
+        #[allow(clippy::should_implement_trait)]
+        pub fn default() -> Self {
+            Self::init(String::from("Default DAO"), GovernanceConfig::default())
+        }
+
+        // Constructor that initializes the values for a DAO taking part in the Super DAO, and
+        // registers it as a member if `auto_join` is set.
+        #[cfg(all(feature = "superdao", not(feature = "prevote")))]
+        #[ink(constructor)]
+        pub fn new_with_superdao(
+            name: String,
+            superdao: AccountId,
+            config: GovernanceConfig,
+            auto_join: bool,
+        ) -> Result<Self, DaoError> {
+            config.validate()?;
+            let mut dao = Self::init(name, config, superdao);
+            if auto_join {
+                dao.register_in_superdao()?;
+            }
+            Ok(dao)
+        }
+
+        // Constructor that initializes the values for a DAO taking part in the Super DAO and
+        // voting with `token` weighted by `strategy`, and registers it as a member if
+        // `auto_join` is set.
+        #[cfg(feature = "prevote")]
+        #[ink(constructor)]
+        pub fn new_with_token(
+            name: String,
+            superdao: AccountId,
+            token: AccountId,
+            config: GovernanceConfig,
+            strategy: Strategy,
+            auto_join: bool,
+        ) -> Result<Self, DaoError> {
+            config.validate()?;
+            let mut dao = Self::init(
+                name,
+                config,
+                superdao,
+                VotingToken::Contract(token),
+                strategy,
+            );
+            if auto_join {
+                dao.register_in_superdao()?;
+            }
+            Ok(dao)
+        }
+
+        // Constructor that initializes the values for a DAO taking part in the Super DAO and
+        // voting with `asset`, a new Pop fungible asset it creates and administers, weighted by
+        // `strategy`, and registers it as a member if `auto_join` is set. The value transferred
+        // covers the deposit for the asset.
+        #[cfg(feature = "prevote")]
+        #[ink(constructor, payable)]
+        pub fn new_with_asset(
+            name: String,
+            superdao: AccountId,
+            asset: NewAsset,
+            config: GovernanceConfig,
+            strategy: Strategy,
+            auto_join: bool,
+        ) -> Result<Self, DaoError> {
+            config.validate()?;
+            let NewAsset {
+                id,
+                min_balance,
+                name: asset_name,
+                symbol,
+                decimals,
+            } = asset;
+            let mut dao = Self::init(name, config, superdao, VotingToken::Asset(id), strategy);
+            api::create(id, dao.env().account_id(), min_balance)
+                .map_err(Psp22Error::from)
+                .map_err(token_error)?;
+            api::set_metadata(id, asset_name.into_bytes(), symbol.into_bytes(), decimals)
+                .map_err(Psp22Error::from)
+                .map_err(token_error)?;
+            if auto_join {
+                dao.register_in_superdao()?;
+            }
+            Ok(dao)
+        }
+
+        // Note: ink! does not evaluate `cfg` on constructor parameters, so each configuration
+        // gets its own constructor and they share this initializer.
+        fn init(
+            name: String,
+            config: GovernanceConfig,
+            #[cfg(feature = "superdao")] superdao: AccountId,
+            #[cfg(feature = "prevote")] token: VotingToken,
+            #[cfg(feature = "prevote")] strategy: Strategy,
+        ) -> Self {
+            let mut dao = Self {
+                name,
+                voters: Membership::default(),
+                config,
+                roles: Roles::default(),
+                applicants: Membership::default(),
+                applications: Mapping::default(),
+                application_reviews: Mapping::default(),
+                next_application_id: 0,
+                deposits: Mapping::default(),
+                total_deposits: 0,
+                #[cfg(feature = "proposals")]
+                proposals: Mapping::default(),
+                #[cfg(feature = "proposals")]
+                vote_counts: Mapping::default(),
+                #[cfg(feature = "proposals")]
+                ballots: Mapping::default(),
+                #[cfg(feature = "proposals")]
+                next_proposal_id: 0,
+                #[cfg(feature = "proposals")]
+                proposal_deposits: 0,
+                #[cfg(feature = "proposals")]
+                pending_slashes: Mapping::default(),
+                #[cfg(feature = "superdao")]
+                superdao,
+                #[cfg(feature = "superdao")]
+                superdao_member: false,
+                #[cfg(all(feature = "superdao", not(feature = "prevote")))]
+                superdao_votes: Mapping::default(),
+                #[cfg(all(feature = "superdao", not(feature = "prevote")))]
+                superdao_ballots: Mapping::default(),
+                #[cfg(feature = "xcm")]
+                cross_chain_proposals: Mapping::default(),
+                #[cfg(feature = "xcm")]
+                superdao_proposals: Mapping::default(),
+                #[cfg(feature = "xcm")]
+                next_cross_chain_proposal_id: 0,
+                #[cfg(feature = "prevote")]
+                token,
+                #[cfg(feature = "prevote")]
+                minted: Mapping::default(),
+                #[cfg(feature = "prevote")]
+                prevotes: Mapping::default(),
+                #[cfg(feature = "prevote")]
+                prevote_ballots: Mapping::default(),
+                #[cfg(feature = "prevote")]
+                prevote_voters: Mapping::default(),
+                #[cfg(feature = "prevote")]
+                prevote_voter_positions: Mapping::default(),
+                #[cfg(feature = "prevote")]
+                strategy,
+                #[cfg(feature = "prevote")]
+                locked: Mapping::default(),
+                #[cfg(feature = "prevote")]
+                delegations: Mapping::default(),
+                #[cfg(feature = "prevote")]
+                delegated: Mapping::default(),
+                #[cfg(feature = "prevote")]
+                delegation_in_use_until: Mapping::default(),
+            };
+
+            // The deployer administers the roles
+            let admin = Self::env().caller();
+            dao.roles
+                .grant(admin, Role::Admin)
+                .expect("no role is granted yet");
+            Self::env().emit_event(RoleGranted {
+                account: admin,
+                role: Role::Admin,
+                by: admin,
+            });
+            dao
+        }
+
+        #[ink(message)]
+        pub fn get_name(&self) -> String {
+            self.name.clone()
+        }
+
+        /// Register the caller as a voter, holding the transferred value as their deposit.
+        /// Unless registration is open, the caller needs the member role.
+        #[ink(message, payable)]
+        pub fn register_voter(&mut self) -> Result<(), DaoError> {
+            let caller = self.env().caller();
+            if self.config.registration != Registration::Open {
+                self.ensure_role(caller, Role::Member)?;
+            }
+            let deposit = self.env().transferred_value();
+            if deposit != self.config.registration_deposit {
+                return Err(DaoError::IncorrectDeposit);
+            }
+            self.add_voter(caller)?;
+            if deposit > 0 {
+                self.deposits.insert(caller, &deposit);
+                self.total_deposits += deposit;
+            }
+            Ok(())
+        }
+
+        /// Apply to become a voter, replacing an expired application of the caller. Approved
+        /// applicants do not pay the registration deposit.
+        #[ink(message)]
+        pub fn apply(&mut self) -> Result<(), DaoError> {
+            let caller = self.env().caller();
+            if self.config.registration != Registration::Application {
+                return Err(DaoError::ApplicationsClosed);
+            }
+            self.ensure_not_voter(caller)?;
+            let now = self.env().block_number();
+            if let Some(application) = self.applications.get(caller) {
+                if now <= application.expires_at {
+                    return Err(DaoError::ApplicationPending);
+                }
+                self.applicants.remove(caller)?;
+            }
+
+            let expires_at = now.saturating_add(self.config.application_period);
+            self.applications.insert(
+                caller,
+                &Application {
+                    id: self.next_application_id,
+                    expires_at,
+                    approvals: 0,
+                    rejections: 0,
+                },
+            );
+            self.next_application_id += 1;
+            self.applicants.add(caller)?;
+            self.env().emit_event(MembershipApplied {
+                applicant: caller,
+                expires_at,
+            });
+            Ok(())
+        }
+
+        /// Approve the application of `applicant`. An admin approves it at once; otherwise it
+        /// is approved once as many voters as the quorum did.
+        #[ink(message)]
+        pub fn approve_member(&mut self, applicant: AccountId) -> Result<(), DaoError> {
+            self.review_application(applicant, true)
+        }
+
+        /// Reject the application of `applicant`. An admin rejects it at once; otherwise it is
+        /// rejected once as many voters as the quorum did.
+        #[ink(message)]
+        pub fn reject_member(&mut self, applicant: AccountId) -> Result<(), DaoError> {
+            self.review_application(applicant, false)
+        }
+
+        #[ink(message)]
+        pub fn get_application(&self, applicant: AccountId) -> Option<Application> {
+            self.applications.get(applicant)
+        }
+
+        /// Returns the applicants among up to `limit` applications, starting from position
+        /// `offset`, whose application has not expired.
+        #[ink(message)]
+        pub fn pending_applications(&self, offset: u32, limit: u32) -> Vec<AccountId> {
+            let now = self.env().block_number();
+            self.applicants
+                .page(offset, limit)
+                .into_iter()
+                .filter(|applicant| {
+                    self.applications
+                        .get(applicant)
+                        .is_some_and(|application| now <= application.expires_at)
+                })
+                .collect()
+        }
+
+        /// Deregister the caller, refunding what is left of their deposit. A voter can't
+        /// deregister while a proposal slashing their deposit is pending, so as not to escape it.
+        #[ink(message)]
+        pub fn deregister_voter(&mut self) -> Result<(), DaoError> {
+            let caller = self.env().caller();
+            #[cfg(feature = "proposals")]
+            if self.pending_slashes.contains(caller) {
+                return Err(DaoError::DepositFrozen);
+            }
+            self.voters.remove(caller)?;
+
+            // Refund what is left of the deposit
+            if let Some(deposit) = self.deposits.take(caller) {
+                self.total_deposits -= deposit;
+                self.env()
+                    .transfer(caller, deposit)
+                    .map_err(|_| DaoError::RefundFailed)?;
+            }
+
+            self.env().emit_event(VoterDeregistered { voter: caller });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn deposit_of(&self, voter: AccountId) -> Balance {
+            self.deposits.get(voter).unwrap_or_default()
+        }
+
+        #[ink(message)]
+        pub fn total_deposits(&self) -> Balance {
+            self.total_deposits
+        }
+
+        #[ink(message)]
+        pub fn has_voter(&self, voter: AccountId) -> bool {
+            self.voters.contains(voter)
+        }
+
+        /// Grant `role` to `account`. Only admins can grant roles.
+        #[ink(message)]
+        pub fn grant_role(&mut self, account: AccountId, role: Role) -> Result<(), DaoError> {
+            let caller = self.env().caller();
+            self.ensure_role(caller, Role::Admin)?;
+            self.roles.grant(account, role)?;
+            self.env().emit_event(RoleGranted {
+                account,
+                role,
+                by: caller,
+            });
+            Ok(())
+        }
+
+        /// Revoke `role` from `account`. Only admins can revoke roles.
+        #[ink(message)]
+        pub fn revoke_role(&mut self, account: AccountId, role: Role) -> Result<(), DaoError> {
+            let caller = self.env().caller();
+            self.ensure_role(caller, Role::Admin)?;
+            self.roles.revoke(account, role)?;
+            self.env().emit_event(RoleRevoked {
+                account,
+                role,
+                by: caller,
+            });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn has_role(&self, account: AccountId, role: Role) -> bool {
+            self.roles.has(account, role)
+        }
+
+        /// Returns up to `limit` registered voters, starting from position `offset`.
+        #[ink(message)]
+        pub fn members(&self, offset: u32, limit: u32) -> Vec<AccountId> {
+            self.voters.page(offset, limit)
+        }
+
+        /// Returns the number of registered voters.
+        #[ink(message)]
+        pub fn member_count(&self) -> u32 {
+            self.voters.count()
+        }
+
+        /// Returns the governance parameters.
+        #[ink(message)]
+        pub fn config(&self) -> GovernanceConfig {
+            self.config
+        }
+
+        /// Create a draft proposal, making `call` once executed, and return its ID. The
+        /// transferred value is the proposal deposit.
+        #[cfg(feature = "proposals")]
+        #[ink(message, payable)]
+        pub fn create_proposal(&mut self, call: Option<ProposalCall>) -> Result<u32, DaoError> {
+            let caller = self.env().caller();
+            self.ensure_can_propose(caller)?;
+            if let Some(call) = &call {
+                call.validate()?;
+                // The changes of a call to the DAO itself would be lost, see `GovernanceAction`,
+                // and so would those of a callee calling back into the DAO
+                if call.callee == self.env().account_id() || call.allow_reentry {
+                    return Err(DaoError::InvalidCall);
+                }
+            }
+            self.insert_proposal(caller, call, None)
+        }
+
+        /// Create a draft proposal applying `action` once executed, and return its ID. The
+        /// transferred value is the proposal deposit.
+        #[cfg(feature = "proposals")]
+        #[ink(message, payable)]
+        pub fn propose_action(&mut self, action: GovernanceAction) -> Result<u32, DaoError> {
+            let caller = self.env().caller();
+            self.ensure_can_propose(caller)?;
+            self.check_action(&action)?;
+            self.insert_proposal(caller, None, Some(action))
+        }
+
+        /// Create a draft proposal registering the DAO in the Super DAO once executed, and
+        /// return its ID. The transferred value is the proposal deposit.
+        #[cfg(feature = "superdao")]
+        #[ink(message, payable)]
+        pub fn propose_join_superdao(&mut self) -> Result<u32, DaoError> {
+            self.propose_action(GovernanceAction::JoinSuperDao)
+        }
+
+        /// Create a draft proposal deregistering the DAO from the Super DAO once executed, and
+        /// return its ID. The transferred value is the proposal deposit.
+        #[cfg(feature = "superdao")]
+        #[ink(message, payable)]
+        pub fn propose_leave_superdao(&mut self) -> Result<u32, DaoError> {
+            self.propose_action(GovernanceAction::LeaveSuperDao)
+        }
+
+        /// Create a draft proposal migrating the DAO to `new_superdao` once executed, and
+        /// return its ID. The transferred value is the proposal deposit.
+        #[cfg(feature = "superdao")]
+        #[ink(message, payable)]
+        pub fn propose_superdao_migration(
+            &mut self,
+            new_superdao: AccountId,
+        ) -> Result<u32, DaoError> {
+            self.propose_action(GovernanceAction::MigrateSuperDao(new_superdao))
+        }
+
+        /// Returns the address of the Super DAO.
+        #[cfg(feature = "superdao")]
+        #[ink(message)]
+        pub fn get_superdao(&self) -> AccountId {
+            self.superdao
+        }
+
+        /// Remove a draft proposal. Only its proposer or a guardian can remove it; the deposit
+        /// is refunded to the proposer, unless a guardian removed it.
+        #[cfg(feature = "proposals")]
+        #[ink(message)]
+        pub fn remove_proposal(&mut self, proposal_id: u32) -> Result<(), DaoError> {
+            let mut proposal = self.get_draft(proposal_id)?;
+            let caller = self.env().caller();
+            if proposal.proposer == caller {
+                self.refund_proposal_deposit(&mut proposal)?;
+            } else if self.roles.has(caller, Role::Guardian) {
+                // The DAO keeps the deposit
+                self.proposal_deposits -= proposal.deposit;
+            } else {
+                return Err(DaoError::NotProposer);
+            }
+
+            self.update_pending_slashes(&proposal, false);
+            self.proposals.remove(proposal_id);
+            Ok(())
+        }
+
+        /// Open a draft proposal for voting during the voting period, after the voting delay.
+        /// Only its proposer can open it.
+        #[cfg(feature = "proposals")]
+        #[ink(message)]
+        pub fn start_proposal(&mut self, proposal_id: u32) -> Result<(), DaoError> {
+            let mut proposal = self.get_draft(proposal_id)?;
+            self.ensure_proposer(&proposal)?;
+
+            let now = self.env().block_number();
+            proposal.status = ProposalStatus::Active;
+            proposal.voting_start = now.saturating_add(self.config.voting_delay);
+            proposal.voting_end = proposal
+                .voting_start
+                .saturating_add(self.config.voting_period);
+            self.proposals.insert(proposal_id, &proposal);
+            Ok(())
+        }
+
+        /// Close the voting of a proposal once its voting period is over, refunding its deposit,
+        /// or expire a succeeded proposal which was not executed in time. Returns the new status
+        /// of the proposal.
+        #[cfg(feature = "proposals")]
+        #[ink(message)]
+        pub fn finalize_proposal(&mut self, proposal_id: u32) -> Result<ProposalStatus, DaoError> {
+            let mut proposal = self
+                .proposals
+                .get(proposal_id)
+                .ok_or(DaoError::ProposalDoesNotExist)?;
+
+            let now = self.env().block_number();
+            proposal.status = match proposal.status {
+                ProposalStatus::Draft => return Err(DaoError::ProposalNotActive),
+                ProposalStatus::Active => {
+                    if now < proposal.voting_end {
+                        return Err(DaoError::VotingPeriodNotEnded);
+                    }
+                    self.refund_proposal_deposit(&mut proposal)?;
+                    if self.config.is_approved(&proposal.tally) {
+                        ProposalStatus::Succeeded
+                    } else {
+                        ProposalStatus::Defeated
+                    }
+                }
+                ProposalStatus::Succeeded => {
+                    if now <= self.execution_deadline(&proposal) {
+                        return Err(DaoError::ExecutionPeriodNotEnded);
+                    }
+                    ProposalStatus::Expired
+                }
+                ProposalStatus::Defeated | ProposalStatus::Executed | ProposalStatus::Expired => {
+                    return Err(DaoError::ProposalAlreadyFinalized)
+                }
+            };
+            if proposal.status != ProposalStatus::Succeeded {
+                self.update_pending_slashes(&proposal, false);
+            }
+            self.proposals.insert(proposal_id, &proposal);
+            self.env().emit_event(ProposalFinalized {
+                proposal_id,
+                status: proposal.status,
+            });
+            Ok(proposal.status)
+        }
+
+        /// Execute a succeeded proposal during its execution period, applying its action and
+        /// making its call, which can't transfer the deposits held by the DAO. The return value
+        /// of the call, or its error, is recorded in the proposal; if the action fails, the
+        /// proposal is left to execute again.
+        #[cfg(feature = "proposals")]
+        #[ink(message)]
+        pub fn execute(&mut self, proposal_id: u32) -> Result<(), DaoError> {
+            self.ensure_voter(self.env().caller())?;
+
+            let mut proposal = self
+                .proposals
+                .get(proposal_id)
+                .ok_or(DaoError::ProposalDoesNotExist)?;
+            if proposal.status != ProposalStatus::Succeeded {
+                return Err(DaoError::ProposalNotSucceeded);
+            }
+            if self.env().block_number() > self.execution_deadline(&proposal) {
+                return Err(DaoError::ExecutionPeriodEnded);
+            }
+
+            if let Some(call) = &proposal.call {
+                // The call can't spend the deposits held for the voters and the proposers
+                let held = self.total_deposits.saturating_add(self.proposal_deposits);
+                if self.env().balance() < held.saturating_add(call.transferred_value) {
+                    return Err(DaoError::InsufficientFunds);
+                }
+            }
+            if let Some(action) = proposal.action.clone() {
+                self.apply_action(action)?;
+            }
+
+            // Mark the proposal executed before the call so it can't be executed again
+            proposal.status = ProposalStatus::Executed;
+            self.update_pending_slashes(&proposal, false);
+            self.proposals.insert(proposal_id, &proposal);
+
+            if let Some(call) = &proposal.call {
+                proposal.outcome = Some(call.invoke());
+                self.proposals.insert(proposal_id, &proposal);
+            }
+            self.env().emit_event(ProposalFinalized {
+                proposal_id,
+                status: proposal.status,
+            });
+            Ok(())
+        }
+
+        #[cfg(feature = "proposals")]
+        #[ink(message)]
+        pub fn get_proposal(&self, proposal_id: u32) -> Option<BasicProposal> {
+            self.proposals.get(proposal_id)
+        }
+
+        #[cfg(feature = "proposals")]
+        #[ink(message)]
+        pub fn vote(&mut self, proposal_id: u32, ballot: Ballot) -> Result<(), DaoError> {
+            let caller = self.env().caller();
+            self.ensure_voter(caller)?;
+            let mut proposal = self.get_open_proposal(proposal_id)?;
+
+            // Check if voter has already voted on this proposal
+            if self.ballots.contains((caller, proposal_id)) {
+                return Err(DaoError::AlreadyVoted);
+            }
+
+            // Update proposal tally
+            proposal.tally.add(ballot);
+            self.proposals.insert(proposal_id, &proposal);
+
+            // Record how this voter voted on this proposal
+            self.ballots.insert((caller, proposal_id), &ballot);
+
+            // Update voter's total vote count
+            let current_votes = self.vote_counts.get(caller).unwrap_or_default();
+            self.vote_counts.insert(caller, &(current_votes + 1));
+
+            self.env().emit_event(VoteCast {
+                proposal_id,
+                voter: caller,
+                ballot: Some(ballot),
+                superdao: false,
+            });
+            Ok(())
+        }
+
+        #[cfg(feature = "proposals")]
+        #[ink(message)]
+        pub fn vote_count(&self, voter: AccountId) -> u32 {
+            self.vote_counts.get(voter).unwrap_or_default()
+        }
+
+        /// Replace the ballot cast by the caller while the proposal is open for voting.
+        #[cfg(feature = "proposals")]
+        #[ink(message)]
+        pub fn change_vote(&mut self, proposal_id: u32, ballot: Ballot) -> Result<(), DaoError> {
+            let caller = self.env().caller();
+            self.ensure_voter(caller)?;
+            let mut proposal = self.get_open_proposal(proposal_id)?;
+            let previous = self
+                .ballots
+                .get((caller, proposal_id))
+                .ok_or(DaoError::NotVoted)?;
+
+            proposal.tally.remove(previous);
+            proposal.tally.add(ballot);
+            self.proposals.insert(proposal_id, &proposal);
+            self.ballots.insert((caller, proposal_id), &ballot);
+            self.env().emit_event(VoteCast {
+                proposal_id,
+                voter: caller,
+                ballot: Some(ballot),
+                superdao: false,
+            });
+            Ok(())
+        }
+
+        /// Withdraw the ballot cast by the caller while the proposal is open for voting.
+        #[cfg(feature = "proposals")]
+        #[ink(message)]
+        pub fn retract_vote(&mut self, proposal_id: u32) -> Result<(), DaoError> {
+            let caller = self.env().caller();
+            self.ensure_voter(caller)?;
+            let mut proposal = self.get_open_proposal(proposal_id)?;
+            let previous = self
+                .ballots
+                .take((caller, proposal_id))
+                .ok_or(DaoError::NotVoted)?;
+
+            proposal.tally.remove(previous);
+            self.proposals.insert(proposal_id, &proposal);
+
+            let current_votes = self.vote_counts.get(caller).unwrap_or_default();
+            self.vote_counts.insert(caller, &(current_votes - 1));
+            self.env().emit_event(VoteCast {
+                proposal_id,
+                voter: caller,
+                ballot: None,
+                superdao: false,
+            });
+            Ok(())
+        }
+
+        /// Returns the ballot cast by `voter` on the proposal, if any.
+        #[cfg(feature = "proposals")]
+        #[ink(message)]
+        pub fn get_vote(&self, proposal_id: u32, voter: AccountId) -> Option<Ballot> {
+            self.ballots.get((voter, proposal_id))
+        }
+
+        /// Returns whether the DAO is a member of the Super DAO.
+        #[cfg(feature = "superdao")]
+        #[ink(message)]
+        pub fn is_superdao_member(&self) -> bool {
+            self.superdao_member
+        }
+
+        /// Propose to the Super DAO to call the message `selector` of `callee` with the
+        /// SCALE-encoded `input`. Returns the ID of the Super DAO proposal.
+        #[cfg(feature = "superdao")]
+        #[ink(message)]
+        pub fn create_contract_call_proposal(
+            &mut self,
+            callee: AccountId,
+            selector: [u8; 4],
+            input: Vec<u8>,
+            transferred_value: Balance,
+            ref_time_limit: u64,
+            allow_reentry: bool,
+        ) -> Result<u32, DaoError> {
+            self.ensure_can_propose(self.env().caller())?;
+
+            let call = ProposalCall {
+                callee,
+                selector,
+                input,
+                transferred_value,
+                ref_time_limit,
+                allow_reentry,
+            };
+            call.validate()?;
+
+            let contract_call = ContractCall {
+                callee: call.callee,
+                selector: call.selector,
+                input: call.input,
+                transferred_value: call.transferred_value,
+                ref_time_limit: call.ref_time_limit,
+                allow_reentry: call.allow_reentry,
+            };
+            self.create_superdao_proposal(Call::Contract(contract_call))
+        }
+
+        /// Propose to the Super DAO to dispatch `call_data` on the parachain `target_chain_id`.
+        /// Returns the local ID of the cross-chain proposal.
+        #[cfg(feature = "xcm")]
+        #[ink(message)]
+        pub fn create_superdao_cross_chain_proposal(
+            &mut self,
+            target_chain_id: u32,
+            call_data: Vec<u8>,
+        ) -> Result<u32, DaoError> {
+            self.ensure_can_propose(self.env().caller())?;
+
+            // Dispatch the call data on the target parachain
+            let dest = MultiLocation::new(1, X1(Parachain(target_chain_id)));
+            let message = xcm::VersionedXcm::V3(Xcm(vec![Transact {
+                origin_kind: OriginKind::SovereignAccount,
+                require_weight_at_most: Weight::from_parts(1_000_000_000, 1_000_000_000),
+                call: call_data.clone().into(),
+            }]));
+            self.create_cross_chain_proposal(dest, message, target_chain_id, call_data)
+        }
+
+        /// Propose to the Super DAO to send a message to `dest`, paying `fee` for the
+        /// execution of `instructions` there; what is left of the fee is deposited to
+        /// `refund_to`, a location seen from `dest`. Returns the local ID of the cross-chain
+        /// proposal.
+        #[cfg(feature = "xcm")]
+        #[ink(message)]
+        pub fn create_xcm_proposal(
+            &mut self,
+            dest: XcmLocation,
+            fee: XcmAsset,
+            weight_limit: XcmWeightLimit,
+            instructions: Vec<XcmInstruction>,
+            refund_to: XcmLocation,
+        ) -> Result<u32, DaoError> {
+            self.ensure_can_propose(self.env().caller())?;
+
+            let chain_id = crate::cross_chain::parachain_id(&dest);
+            let dest = crate::cross_chain::location(dest)?;
+            let message =
+                crate::cross_chain::build_message(fee, weight_limit, instructions, refund_to)?;
+            let call_data = scale::Encode::encode(&message);
+            self.create_cross_chain_proposal(dest, message, chain_id, call_data)
+        }
+
+        /// Returns the cross-chain proposal with the local ID `proposal_id`.
+        #[cfg(feature = "xcm")]
+        #[ink(message)]
+        pub fn get_cross_chain_proposal(
+            &self,
+            proposal_id: u32,
+        ) -> Result<CrossChainProposal, DaoError> {
+            self.cross_chain_proposals
+                .get(proposal_id)
+                .ok_or(DaoError::ProposalDoesNotExist)
+        }
+
+        /// Returns the local ID of the cross-chain proposal created as the Super DAO proposal
+        /// `superdao_id`.
+        #[cfg(feature = "xcm")]
+        #[ink(message)]
+        pub fn get_cross_chain_proposal_id(&self, superdao_id: u32) -> Option<u32> {
+            self.superdao_proposals.get((self.superdao, superdao_id))
+        }
+
+        /// Update the status of the cross-chain proposal `local_id` from the Super DAO, and
+        /// return it.
+        #[cfg(feature = "xcm")]
+        #[ink(message)]
+        pub fn sync_proposal(&mut self, local_id: u32) -> Result<ProposalStatus, DaoError> {
+            let mut proposal = self.get_cross_chain_proposal(local_id)?;
+            let superdao: contract_ref!(SuperDao) = proposal.superdao.into();
+            let superdao_proposal = flatten_call(
+                superdao
+                    .call()
+                    .get_proposal(proposal.superdao_id)
+                    .try_invoke(),
+            )
+            .map_err(DaoError::SuperDaoCallFailed)?
+            .ok_or(DaoError::ProposalDoesNotExist)?;
+
+            proposal.status = match superdao_proposal.status {
+                superdao_traits::ProposalStatus::Active => ProposalStatus::Active,
+                superdao_traits::ProposalStatus::Approved => ProposalStatus::Succeeded,
+                superdao_traits::ProposalStatus::Rejected => ProposalStatus::Defeated,
+                superdao_traits::ProposalStatus::Executed => ProposalStatus::Executed,
+            };
+            self.cross_chain_proposals.insert(local_id, &proposal);
+            Ok(proposal.status)
+        }
+
+        /// Prevote with `amount` tokens and the tokens delegated to the caller, weighted by the
+        /// voting strategy of the prevote. The DAO locks the tokens of the caller until the
+        /// prevote deadline, and `conviction` more voting periods when the strategy rewards
+        /// convictions; the delegated tokens count without conviction and stay delegated until
+        /// the deadline. The caller must have allowed the DAO, or the contract of a PSP22 voting
+        /// token, to transfer their tokens. With a PSP22 voting token, the caller can't prevote
+        /// with more tokens than they held at the snapshot of the prevote.
+        #[cfg(feature = "prevote")]
+        #[ink(message)]
+        pub fn submit_prevote(
+            &mut self,
+            proposal_id: u32,
+            approved: bool,
+            amount: Balance,
+            conviction: u8,
+        ) -> Result<(), DaoError> {
+            let caller = self.env().caller();
+            self.ensure_voter(caller)?;
+
+            let mut prevote = self.get_open_prevote(proposal_id)?;
+            if self
+                .prevote_ballots
+                .contains((caller, self.superdao, proposal_id))
+            {
+                return Err(DaoError::AlreadyVoted);
+            }
+            if conviction > prevote.strategy.max_conviction() {
+                return Err(DaoError::InvalidConviction);
+            }
+            if let Some(balance) = self.snapshot_balance(caller, prevote.snapshot)? {
+                if amount > balance {
+                    return Err(DaoError::ExceedsSnapshotBalance);
+                }
+            }
+            let unlocks_at = prevote.deadline.saturating_add(
+                BlockNumber::from(conviction).saturating_mul(self.config.voting_period),
+            );
+
+            // Escrow the tokens, so that they can't vote again from another account
+            self.escrow_tokens(caller, amount)?;
+
+            // Count the delegated tokens without conviction: they can't be taken back until the
+            // prevote ends, but the delegate can't lock them any longer
+            let delegated = self.delegated.get(caller).unwrap_or_default();
+            if delegated > 0 {
+                let in_use_until = self.delegation_in_use_until.get(caller).unwrap_or_default();
+                self.delegation_in_use_until
+                    .insert(caller, &in_use_until.max(prevote.deadline));
+            }
+
+            let weight = prevote
+                .strategy
+                .weight_with_delegations(amount, delegated, conviction);
+            self.locked.insert(
+                (caller, self.superdao, proposal_id),
+                &TokenLock {
+                    amount,
+                    weight,
+                    unlocks_at,
+                },
+            );
+            let ballot = if approved { Ballot::Aye } else { Ballot::Nay };
+            prevote.add(ballot, weight);
+            self.prevote_ballots
+                .insert((caller, self.superdao, proposal_id), &ballot);
+            self.prevote_voters
+                .insert((self.superdao, proposal_id, prevote.voter_count), &caller);
+            self.prevote_voter_positions
+                .insert((caller, self.superdao, proposal_id), &prevote.voter_count);
+            prevote.voter_count += 1;
+            self.prevotes.insert((self.superdao, proposal_id), &prevote);
+            self.env().emit_event(PrevoteSubmitted {
+                proposal_id,
+                voter: caller,
+                approved: Some(approved),
+                weight,
+            });
+            Ok(())
+        }
+
+        /// Replace the prevote of the caller before the prevote deadline. The vote keeps the
+        /// weight it was submitted with.
+        #[cfg(feature = "prevote")]
+        #[ink(message)]
+        pub fn change_prevote(&mut self, proposal_id: u32, approved: bool) -> Result<(), DaoError> {
+            let caller = self.env().caller();
+            self.ensure_voter(caller)?;
+
+            let mut prevote = self.get_open_prevote(proposal_id)?;
+            let previous = self
+                .prevote_ballots
+                .get((caller, self.superdao, proposal_id))
+                .ok_or(DaoError::NotVoted)?;
+            let weight = self.prevote_weight(caller, proposal_id);
+            let ballot = if approved { Ballot::Aye } else { Ballot::Nay };
+            prevote.remove(previous, weight);
+            prevote.add(ballot, weight);
+            self.prevote_ballots
+                .insert((caller, self.superdao, proposal_id), &ballot);
+            self.prevotes.insert((self.superdao, proposal_id), &prevote);
+            self.env().emit_event(PrevoteSubmitted {
+                proposal_id,
+                voter: caller,
+                approved: Some(approved),
+                weight,
+            });
+            Ok(())
+        }
+
+        /// Withdraw the prevote of the caller before the prevote deadline, unlocking its tokens.
+        #[cfg(feature = "prevote")]
+        #[ink(message)]
+        pub fn retract_prevote(&mut self, proposal_id: u32) -> Result<(), DaoError> {
+            let caller = self.env().caller();
+            self.ensure_voter(caller)?;
+
+            let mut prevote = self.get_open_prevote(proposal_id)?;
+            let ballot = self
+                .prevote_ballots
+                .take((caller, self.superdao, proposal_id))
+                .ok_or(DaoError::NotVoted)?;
+            let weight = self.prevote_weight(caller, proposal_id);
+            prevote.remove(ballot, weight);
+
+            // Move the last voter into the position of the caller
+            let position = self
+                .prevote_voter_positions
+                .take((caller, self.superdao, proposal_id))
+                .expect("every voter has a position");
+            prevote.voter_count -= 1;
+            if position != prevote.voter_count {
+                let moved = self
+                    .prevote_voters
+                    .get((self.superdao, proposal_id, prevote.voter_count))
+                    .expect("every position below `voter_count` holds a voter");
+                self.prevote_voters
+                    .insert((self.superdao, proposal_id, position), &moved);
+                self.prevote_voter_positions
+                    .insert((moved, self.superdao, proposal_id), &position);
+            }
+            self.prevote_voters
+                .remove((self.superdao, proposal_id, prevote.voter_count));
+            self.prevotes.insert((self.superdao, proposal_id), &prevote);
+            self.env().emit_event(PrevoteSubmitted {
+                proposal_id,
+                voter: caller,
+                approved: None,
+                weight,
+            });
+            self.unlock_tokens(caller, self.superdao, proposal_id)
+        }
+
+        /// Return the tokens locked by the prevote of the caller once they unlock, after the
+        /// prevote deadline. Voters who since left the DAO can still withdraw them.
+        #[cfg(feature = "prevote")]
+        #[ink(message)]
+        pub fn withdraw_locked(&mut self, proposal_id: u32) -> Result<(), DaoError> {
+            self.withdraw_locked_from(self.superdao, proposal_id)
+        }
+
+        /// Return the tokens locked by the prevote of the caller on the proposal `proposal_id`
+        /// of `superdao`, which may be a Super DAO the DAO has since migrated away from.
+        #[cfg(feature = "prevote")]
+        #[ink(message)]
+        pub fn withdraw_locked_from(
+            &mut self,
+            superdao: AccountId,
+            proposal_id: u32,
+        ) -> Result<(), DaoError> {
+            let caller = self.env().caller();
+            let prevote = self
+                .prevotes
+                .get((superdao, proposal_id))
+                .ok_or(DaoError::ProposalDoesNotExist)?;
+            if self.env().block_number() < prevote.deadline {
+                return Err(DaoError::VotingPeriodNotEnded);
+            }
+            let lock = self
+                .locked
+                .get((caller, superdao, proposal_id))
+                .ok_or(DaoError::NotVoted)?;
+            if self.env().block_number() < lock.unlocks_at {
+                return Err(DaoError::TokensLocked);
+            }
+            self.unlock_tokens(caller, superdao, proposal_id)
+        }
+
+        #[cfg(feature = "prevote")]
+        #[ink(message)]
+        pub fn get_voting_strategy(&self) -> Strategy {
+            self.strategy
+        }
+
+        #[cfg(feature = "prevote")]
+        #[ink(message)]
+        pub fn get_voting_token(&self) -> VotingToken {
+            self.token
+        }
+
+        /// Delegate all the tokens of the caller to `delegate`, whose prevotes count them. The DAO
+        /// escrows the tokens, so the caller must have allowed the DAO, or the contract of a PSP22
+        /// voting token, to transfer them.
+        #[cfg(feature = "prevote")]
+        #[ink(message)]
+        pub fn delegate(&mut self, delegate: AccountId) -> Result<(), DaoError> {
+            let caller = self.env().caller();
+            self.ensure_voter(caller)?;
+            self.ensure_voter(delegate)?;
+            if caller == delegate {
+                return Err(DaoError::SelfDelegation);
+            }
+            if self.delegations.contains(caller) {
+                return Err(DaoError::AlreadyDelegated);
+            }
+            // Only direct delegations, which also rules out cycles
+            if self.delegations.contains(delegate) || self.delegated.contains(caller) {
+                return Err(DaoError::DelegationChain);
+            }
+
+            let amount = self.token_balance(caller)?;
+            if amount == 0 {
+                return Err(DaoError::NothingToDelegate);
+            }
+            self.escrow_tokens(caller, amount)?;
+
+            self.delegations
+                .insert(caller, &Delegation { delegate, amount });
+            let delegated = self.delegated.get(delegate).unwrap_or_default();
+            self.delegated.insert(delegate, &(delegated + amount));
+            self.env().emit_event(DelegationChanged {
+                delegator: caller,
+                delegate: Some(delegate),
+                amount,
+            });
+            Ok(())
+        }
+
+        /// Take back the tokens delegated by the caller, once no open prevote of their delegate
+        /// counts them.
+        #[cfg(feature = "prevote")]
+        #[ink(message)]
+        pub fn undelegate(&mut self) -> Result<(), DaoError> {
+            let caller = self.env().caller();
+            let Delegation { delegate, amount } =
+                self.delegations.get(caller).ok_or(DaoError::NotDelegated)?;
+            let in_use_until = self
+                .delegation_in_use_until
+                .get(delegate)
+                .unwrap_or_default();
+            if self.env().block_number() < in_use_until {
+                return Err(DaoError::DelegationInUse);
+            }
+
+            self.delegations.remove(caller);
+
+            let delegated = self.delegated.get(delegate).unwrap_or_default() - amount;
+            if delegated == 0 {
+                self.delegated.remove(delegate);
+                self.delegation_in_use_until.remove(delegate);
+            } else {
+                self.delegated.insert(delegate, &delegated);
+            }
+            self.release_tokens(caller, amount)?;
+            self.env().emit_event(DelegationChanged {
+                delegator: caller,
+                delegate: None,
+                amount,
+            });
+            Ok(())
+        }
+
+        #[cfg(feature = "prevote")]
+        #[ink(message)]
+        pub fn get_delegation(&self, delegator: AccountId) -> Option<Delegation> {
+            self.delegations.get(delegator)
+        }
+
+        /// Returns the weight `account` would prevote with, without conviction, on a prevote
+        /// opened now: its own tokens and those delegated to it, weighted by the current voting
+        /// strategy.
+        #[cfg(feature = "prevote")]
+        #[ink(message)]
+        pub fn voting_power(&self, account: AccountId) -> Result<Balance, DaoError> {
+            let balance = self.token_balance(account)?;
+            let delegated = self.delegated.get(account).unwrap_or_default();
+            Ok(self.strategy.weight_with_delegations(balance, delegated, 0))
+        }
+
+        #[cfg(feature = "prevote")]
+        #[ink(message)]
+        pub fn get_prevote(&self, proposal_id: u32) -> Option<Prevote> {
+            self.prevotes.get((self.superdao, proposal_id))
+        }
+
+        #[cfg(feature = "prevote")]
+        #[ink(message)]
+        pub fn get_prevote_ballot(&self, proposal_id: u32, voter: AccountId) -> Option<Ballot> {
+            self.prevote_ballots
+                .get((voter, self.superdao, proposal_id))
+        }
+
+        /// Returns up to `limit` voters of the prevote of `proposal_id`, starting from position
+        /// `offset`.
+        #[cfg(feature = "prevote")]
+        #[ink(message)]
+        pub fn prevote_voters(&self, proposal_id: u32, offset: u32, limit: u32) -> Vec<AccountId> {
+            let count = self
+                .prevotes
+                .get((self.superdao, proposal_id))
+                .map(|prevote| prevote.voter_count)
+                .unwrap_or_default();
+            let end = offset.saturating_add(limit).min(count);
+            (offset..end)
+                .filter_map(|position| {
+                    self.prevote_voters
+                        .get((self.superdao, proposal_id, position))
+                })
+                .collect()
+        }
+
+        /// Returns the tokens `voter` has locked on the prevote of `proposal_id`.
+        #[cfg(feature = "prevote")]
+        #[ink(message)]
+        pub fn locked_balance(&self, voter: AccountId, proposal_id: u32) -> Balance {
+            self.locked
+                .get((voter, self.superdao, proposal_id))
+                .map(|lock| lock.amount)
+                .unwrap_or_default()
+        }
+
+        /// Vote internally on the Super DAO proposal `proposal_id`. The first vote opens the
+        /// internal vote for the voting period, ending before the voting period of the Super DAO
+        /// proposal does; the DAO vote is then cast on the Super DAO by `finalize_superdao_vote`.
+        /// Abstentions count towards the quorum only.
+        #[cfg(all(feature = "superdao", not(feature = "prevote")))]
+        #[ink(message)]
+        pub fn vote_proposal(&mut self, proposal_id: u32, ballot: Ballot) -> Result<(), DaoError> {
+            let caller = self.env().caller();
+            self.ensure_voter(caller)?;
+
+            let mut vote = match self.superdao_votes.get((self.superdao, proposal_id)) {
+                Some(vote) => vote,
+                None => {
+                    let voting_end = self
+                        .env()
+                        .block_number()
+                        .saturating_add(self.config.voting_period)
+                        .min(self.superdao_voting_end(proposal_id)?);
+                    SuperDaoVote {
+                        voting_end,
+                        tally: Tally::default(),
+                        outcome: None,
+                    }
+                }
+            };
+            if self.env().block_number() >= vote.voting_end {
+                return Err(DaoError::VotingPeriodEnded);
+            }
+            if self
+                .superdao_ballots
+                .contains((caller, self.superdao, proposal_id))
+            {
+                return Err(DaoError::AlreadyVoted);
+            }
+
+            vote.tally.add(ballot);
+            self.superdao_votes
+                .insert((self.superdao, proposal_id), &vote);
+            self.superdao_ballots
+                .insert((caller, self.superdao, proposal_id), &ballot);
+
+            // Update local vote count of the cross-chain proposal, if it is ours
+            #[cfg(feature = "xcm")]
+            if let Some(local_id) = self.superdao_proposals.get((self.superdao, proposal_id)) {
+                let mut proposal = self.get_cross_chain_proposal(local_id)?;
+                match ballot {
+                    Ballot::Aye => proposal.votes_yes += 1,
+                    Ballot::Nay => proposal.votes_no += 1,
+                    Ballot::Abstain => {}
+                }
+                self.cross_chain_proposals.insert(local_id, &proposal);
+            }
+
+            self.env().emit_event(VoteCast {
+                proposal_id,
+                voter: caller,
+                ballot: Some(ballot),
+                superdao: true,
+            });
+            Ok(())
+        }
+
+        /// Cast the DAO vote on the Super DAO proposal once its internal vote is over, and
+        /// return it. The governance quorum and approval threshold decide between aye and nay;
+        /// the DAO abstains, casting no vote, when the quorum is not reached.
+        #[cfg(all(feature = "superdao", not(feature = "prevote")))]
+        #[ink(message)]
+        pub fn finalize_superdao_vote(&mut self, proposal_id: u32) -> Result<Ballot, DaoError> {
+            self.ensure_voter(self.env().caller())?;
+
+            let mut vote = self
+                .superdao_votes
+                .get((self.superdao, proposal_id))
+                .ok_or(DaoError::ProposalDoesNotExist)?;
+            if vote.outcome.is_some() {
+                return Err(DaoError::ProposalAlreadyFinalized);
+            }
+            if self.env().block_number() < vote.voting_end {
+                return Err(DaoError::VotingPeriodNotEnded);
+            }
+
+            let outcome = if vote.tally.total() < self.config.quorum {
+                Ballot::Abstain
+            } else if self.config.is_approved(&vote.tally) {
+                Ballot::Aye
+            } else {
+                Ballot::Nay
+            };
+            vote.outcome = Some(outcome);
+            self.superdao_votes
+                .insert((self.superdao, proposal_id), &vote);
+            self.env().emit_event(SuperDaoVoteCast {
+                proposal_id,
+                vote: outcome,
+            });
+
+            // Cast the vote in the Super DAO
+            let superdao_vote = match outcome {
+                Ballot::Aye => Vote::Aye,
+                Ballot::Nay => Vote::Nay,
+                Ballot::Abstain => return Ok(outcome),
+            };
+            let mut superdao = self.superdao();
+            superdao_result(
+                superdao
+                    .call_mut()
+                    .vote(proposal_id, superdao_vote)
+                    .try_invoke(),
+            )?;
+            Ok(outcome)
+        }
+
+        /// Returns the internal vote on the Super DAO proposal, if any.
+        #[cfg(all(feature = "superdao", not(feature = "prevote")))]
+        #[ink(message)]
+        pub fn get_superdao_vote(&self, proposal_id: u32) -> Option<SuperDaoVote> {
+            self.superdao_votes.get((self.superdao, proposal_id))
+        }
+
+        /// Cast the DAO vote on the Super DAO proposal once its prevote is over, and return it.
+        /// Anyone can finalize a prevote, once. The governance approval threshold applies to
+        /// the weights of the ayes and nays; the DAO abstains, casting no vote, when fewer
+        /// voters than the quorum took part.
+        #[cfg(feature = "prevote")]
+        #[ink(message)]
+        pub fn finalize_prevote(&mut self, proposal_id: u32) -> Result<Ballot, DaoError> {
+            let mut prevote = self
+                .prevotes
+                .get((self.superdao, proposal_id))
+                .ok_or(DaoError::ProposalDoesNotExist)?;
+            if prevote.outcome.is_some() {
+                return Err(DaoError::ProposalAlreadyFinalized);
+            }
+            if self.env().block_number() < prevote.deadline {
+                return Err(DaoError::VotingPeriodNotEnded);
+            }
+
+            let outcome = if prevote.voter_count < self.config.quorum {
+                Ballot::Abstain
+            } else if self
+                .config
+                .is_approved_by_weight(prevote.aye_weight, prevote.nay_weight)
+            {
+                Ballot::Aye
+            } else {
+                Ballot::Nay
+            };
+            prevote.outcome = Some(outcome);
+            self.prevotes.insert((self.superdao, proposal_id), &prevote);
+            self.env().emit_event(SuperDaoVoteCast {
+                proposal_id,
+                vote: outcome,
+            });
+
+            // Cast the vote in the Super DAO
+            let superdao_vote = match outcome {
+                Ballot::Aye => Vote::Aye,
+                Ballot::Nay => Vote::Nay,
+                Ballot::Abstain => return Ok(outcome),
+            };
+            let mut superdao = self.superdao();
+            superdao_result(
+                superdao
+                    .call_mut()
+                    .vote(proposal_id, superdao_vote)
+                    .try_invoke(),
+            )?;
+            Ok(outcome)
+        }
+
+        /// Returns the vote cast on the Super DAO once the prevote of `proposal_id` is
+        /// finalized.
+        #[cfg(feature = "prevote")]
+        #[ink(message)]
+        pub fn prevote_result(&self, proposal_id: u32) -> Option<Ballot> {
+            self.prevotes
+                .get((self.superdao, proposal_id))
+                .and_then(|prevote| prevote.outcome)
+        }
+
+        /// Check if the account has the role.
+        fn ensure_role(&self, account: AccountId, role: Role) -> Result<(), DaoError> {
+            self.roles.ensure(account, role)
+        }
+
+        /// Check that the account is not a registered voter.
+        fn ensure_not_voter(&self, account: AccountId) -> Result<(), DaoError> {
+            if self.has_voter(account) {
+                return Err(DaoError::VoterAlreadyRegistered);
+            }
+            Ok(())
+        }
+
+        /// Register `voter`, minting them tokens under prevotes.
+        fn add_voter(&mut self, voter: AccountId) -> Result<(), DaoError> {
+            self.voters.add(voter)?;
+
+            // Mint tokens for the new voter, once per account so registering again after
+            // deregistering doesn't mint more voting power
+            #[cfg(feature = "prevote")]
+            if !self.minted.contains(voter) {
+                self.mint_tokens(voter, self.config.mint_amount)?;
+                self.minted.insert(voter, &());
+            }
+
+            self.env().emit_event(VoterRegistered { voter });
+            Ok(())
+        }
+
+        /// Record the review of the caller on the application of `applicant`, and close it if
+        /// the caller is an admin or enough voters agreed.
+        fn review_application(
+            &mut self,
+            applicant: AccountId,
+            approved: bool,
+        ) -> Result<(), DaoError> {
+            let caller = self.env().caller();
+            let admin = self.roles.has(caller, Role::Admin);
+            if !admin {
+                self.ensure_voter(caller)?;
+            }
+            let mut application = self
+                .applications
+                .get(applicant)
+                .ok_or(DaoError::ApplicationDoesNotExist)?;
+            if self.env().block_number() > application.expires_at {
+                return Err(DaoError::ApplicationExpired);
+            }
+            if self.application_reviews.contains((caller, application.id)) {
+                return Err(DaoError::ApplicationAlreadyReviewed);
+            }
+
+            self.application_reviews
+                .insert((caller, application.id), &());
+            let reviews = if approved {
+                application.approvals += 1;
+                application.approvals
+            } else {
+                application.rejections += 1;
+                application.rejections
+            };
+            self.env().emit_event(ApplicationReviewed {
+                applicant,
+                reviewer: caller,
+                approved,
+            });
+
+            if !admin && reviews < self.config.quorum {
+                self.applications.insert(applicant, &application);
+                return Ok(());
+            }
+            self.applications.remove(applicant);
+            self.applicants.remove(applicant)?;
+            if approved {
+                self.add_voter(applicant)?;
+            }
+            self.env().emit_event(ApplicationClosed {
+                applicant,
+                approved,
+            });
+            Ok(())
+        }
+
+        /// Check if the account can create proposals: a registered voter, with the proposer
+        /// role unless proposal creation is open.
+        #[cfg(feature = "proposals")]
+        fn ensure_can_propose(&self, account: AccountId) -> Result<(), DaoError> {
+            self.ensure_voter(account)?;
+            if !self.config.open_proposals {
+                self.ensure_role(account, Role::Proposer)?;
+            }
+            Ok(())
+        }
+
+        /// Check if the account is a registered voter.
+        fn ensure_voter(&self, account: AccountId) -> Result<(), DaoError> {
+            if !self.has_voter(account) {
+                return Err(DaoError::VoterNotRegistered);
+            }
+            Ok(())
+        }
+
+        /// Store a new draft proposal, holding the transferred value as its deposit, and return
+        /// its ID.
+        #[cfg(feature = "proposals")]
+        fn insert_proposal(
+            &mut self,
+            proposer: AccountId,
+            call: Option<ProposalCall>,
+            action: Option<GovernanceAction>,
+        ) -> Result<u32, DaoError> {
+            let deposit = self.env().transferred_value();
+            if deposit != self.config.proposal_deposit {
+                return Err(DaoError::IncorrectDeposit);
+            }
+
+            let proposal_id = self.next_proposal_id;
+            let proposal = BasicProposal {
+                proposer,
+                status: ProposalStatus::Draft,
+                voting_start: 0,
+                voting_end: 0,
+                tally: Tally::default(),
+                call,
+                outcome: None,
+                deposit,
+                action,
+            };
+            self.update_pending_slashes(&proposal, true);
+            self.proposals.insert(proposal_id, &proposal);
+            self.next_proposal_id += 1;
+            self.proposal_deposits += deposit;
+            self.env().emit_event(ProposalCreated {
+                proposal_id,
+                proposer,
+                superdao: false,
+            });
+            Ok(proposal_id)
+        }
+
+        /// Count a proposal slashing a deposit among the pending slashes of its voter while it
+        /// can still be executed, and no longer once `pending` is false.
+        #[cfg(feature = "proposals")]
+        fn update_pending_slashes(&mut self, proposal: &BasicProposal, pending: bool) {
+            if let Some(GovernanceAction::SlashDeposit { voter, .. }) = proposal.action {
+                let count = self.pending_slashes.get(voter).unwrap_or_default();
+                let count = if pending { count + 1 } else { count - 1 };
+                if count == 0 {
+                    self.pending_slashes.remove(voter);
+                } else {
+                    self.pending_slashes.insert(voter, &count);
+                }
+            }
+        }
+
+        /// Transfer the deposit of the proposal back to its proposer.
+        #[cfg(feature = "proposals")]
+        fn refund_proposal_deposit(
+            &mut self,
+            proposal: &mut BasicProposal,
+        ) -> Result<(), DaoError> {
+            let deposit = core::mem::take(&mut proposal.deposit);
+            if deposit > 0 {
+                self.proposal_deposits -= deposit;
+                self.env()
+                    .transfer(proposal.proposer, deposit)
+                    .map_err(|_| DaoError::RefundFailed)?;
+            }
+            Ok(())
+        }
+
+        /// Get a proposal which is still a draft.
+        #[cfg(feature = "proposals")]
+        fn get_draft(&self, proposal_id: u32) -> Result<BasicProposal, DaoError> {
+            let proposal = self
+                .proposals
+                .get(proposal_id)
+                .ok_or(DaoError::ProposalDoesNotExist)?;
+            if proposal.status != ProposalStatus::Draft {
+                return Err(DaoError::ProposalNotDraft);
+            }
+            Ok(proposal)
+        }
+
+        /// Get a proposal which is open for voting.
+        #[cfg(feature = "proposals")]
+        fn get_open_proposal(&self, proposal_id: u32) -> Result<BasicProposal, DaoError> {
+            let proposal = self
+                .proposals
+                .get(proposal_id)
+                .ok_or(DaoError::ProposalDoesNotExist)?;
+            if proposal.status != ProposalStatus::Active {
+                return Err(DaoError::ProposalNotActive);
+            }
+            if self.env().block_number() < proposal.voting_start {
+                return Err(DaoError::VotingNotStarted);
+            }
+            if self.env().block_number() >= proposal.voting_end {
+                return Err(DaoError::VotingPeriodEnded);
+            }
+            Ok(proposal)
+        }
+
+        /// Check if the caller is the proposer of the proposal.
+        #[cfg(feature = "proposals")]
+        fn ensure_proposer(&self, proposal: &BasicProposal) -> Result<(), DaoError> {
+            if proposal.proposer != self.env().caller() {
+                return Err(DaoError::NotProposer);
+            }
+            Ok(())
+        }
+
+        /// Last block at which a succeeded proposal can be executed.
+        #[cfg(feature = "proposals")]
+        fn execution_deadline(&self, proposal: &BasicProposal) -> BlockNumber {
+            proposal
+                .voting_end
+                .saturating_add(self.config.execution_period)
+        }
+
+        /// Get a prevote which is still open for voting.
+        #[cfg(feature = "prevote")]
+        fn get_open_prevote(&self, proposal_id: u32) -> Result<Prevote, DaoError> {
+            let prevote = self
+                .prevotes
+                .get((self.superdao, proposal_id))
+                .ok_or(DaoError::ProposalDoesNotExist)?;
+            if self.env().block_number() < prevote.voting_start {
+                return Err(DaoError::VotingNotStarted);
+            }
+            if self.env().block_number() >= prevote.deadline {
+                return Err(DaoError::VotingPeriodEnded);
+            }
+            Ok(prevote)
+        }
+
+        /// Returns the weight of the prevote of `voter` on `proposal_id`.
+        #[cfg(feature = "prevote")]
+        fn prevote_weight(&self, voter: AccountId, proposal_id: u32) -> Balance {
+            self.locked
+                .get((voter, self.superdao, proposal_id))
+                .expect("every prevote locks tokens")
+                .weight
+        }
+
+        /// Transfer back the tokens `voter` locked on the prevote of the proposal `proposal_id`
+        /// of `superdao`.
+        #[cfg(feature = "prevote")]
+        fn unlock_tokens(
+            &mut self,
+            voter: AccountId,
+            superdao: AccountId,
+            proposal_id: u32,
+        ) -> Result<(), DaoError> {
+            let TokenLock { amount, .. } = self
+                .locked
+                .take((voter, superdao, proposal_id))
+                .ok_or(DaoError::NotVoted)?;
+            self.release_tokens(voter, amount)?;
+            self.env().emit_event(TokensUnlocked {
+                proposal_id,
+                voter,
+                amount,
+            });
+            Ok(())
+        }
+
+        /// Returns the voting tokens held by `account`.
+        #[cfg(feature = "prevote")]
+        fn token_balance(&self, account: AccountId) -> Result<Balance, DaoError> {
+            match self.token {
+                VotingToken::Contract(token) => {
+                    let token: contract_ref!(Psp22) = token.into();
+                    flatten_call(token.call().balance_of(account).try_invoke())
+                        .map_err(DaoError::TokenCallFailed)
+                }
+                VotingToken::Asset(id) => api::balance_of(id, account)
+                    .map_err(Psp22Error::from)
+                    .map_err(token_error),
+            }
+        }
+
+        /// Returns the voting tokens held by `account` at the end of `block`, if the token
+        /// records them. The PSP22 contract only records the balances it changes itself, not
+        /// those changed directly through the runtime; fungible assets record none.
+        #[cfg(feature = "prevote")]
+        fn snapshot_balance(
+            &self,
+            account: AccountId,
+            block: BlockNumber,
+        ) -> Result<Option<Balance>, DaoError> {
+            match self.token {
+                VotingToken::Contract(token) => {
+                    // `balance_of_at` is a message of the PSP22 contract itself, not of a trait
+                    let balance = build_call::<DefaultEnvironment>()
+                        .call(token)
+                        .exec_input(
+                            ExecutionInput::new(Selector::new(ink::selector_bytes!(
+                                "balance_of_at"
+                            )))
+                            .push_arg(account)
+                            .push_arg(block),
+                        )
+                        .returns::<Balance>()
+                        .try_invoke();
+                    flatten_call(balance)
+                        .map(Some)
+                        .map_err(DaoError::TokenCallFailed)
+                }
+                VotingToken::Asset(_) => Ok(None),
+            }
+        }
+
+        /// Mint `amount` voting tokens to `account`.
+        #[cfg(feature = "prevote")]
+        fn mint_tokens(&mut self, account: AccountId, amount: Balance) -> Result<(), DaoError> {
+            match self.token {
+                VotingToken::Contract(token) => {
+                    let mut token: contract_ref!(Psp22Mintable) = token.into();
+                    token_result(token.call_mut().mint(account, amount).try_invoke())
+                }
+                VotingToken::Asset(id) => api::mint(id, account, amount)
+                    .map_err(Psp22Error::from)
+                    .map_err(token_error),
+            }
+        }
+
+        /// Escrow `amount` voting tokens of `owner`, who must have allowed the DAO to spend them,
+        /// or the token contract for a PSP22 token. The token contract holds the tokens it
+        /// escrows, as it can only transfer tokens from its own account.
+        #[cfg(feature = "prevote")]
+        fn escrow_tokens(&mut self, owner: AccountId, amount: Balance) -> Result<(), DaoError> {
+            match self.token {
+                VotingToken::Contract(address) => {
+                    let mut token: contract_ref!(Psp22) = address.into();
+                    token_result(
+                        token
+                            .call_mut()
+                            .transfer_from(owner, address, amount, Vec::new())
+                            .try_invoke(),
+                    )
+                }
+                VotingToken::Asset(id) => {
+                    api::transfer_from(id, owner, self.env().account_id(), amount)
+                        .map_err(Psp22Error::from)
+                        .map_err(token_error)
+                }
+            }
+        }
+
+        /// Transfer back `amount` escrowed voting tokens to `owner`, from the DAO or the token
+        /// contract holding them.
+        #[cfg(feature = "prevote")]
+        fn release_tokens(&mut self, owner: AccountId, amount: Balance) -> Result<(), DaoError> {
+            match self.token {
+                VotingToken::Contract(token) => {
+                    let mut token: contract_ref!(Psp22) = token.into();
+                    token_result(
+                        token
+                            .call_mut()
+                            .transfer(owner, amount, Vec::new())
+                            .try_invoke(),
+                    )
+                }
+                VotingToken::Asset(id) => api::transfer(id, owner, amount)
+                    .map_err(Psp22Error::from)
+                    .map_err(token_error),
+            }
+        }
+
+        /// Check that `new_superdao` can replace the current Super DAO.
+        #[cfg(feature = "superdao")]
+        fn ensure_new_superdao(&self, new_superdao: AccountId) -> Result<(), DaoError> {
+            if new_superdao == AccountId::from([0; 32]) || new_superdao == self.superdao {
+                return Err(DaoError::InvalidSuperDaoAddress);
+            }
+            Ok(())
+        }
+
+        /// Register the DAO as a member of the Super DAO.
+        #[cfg(feature = "superdao")]
+        fn register_in_superdao(&mut self) -> Result<(), DaoError> {
+            if self.superdao_member {
+                return Err(DaoError::AlreadySuperDaoMember);
+            }
+
+            let mut superdao = self.superdao();
+            superdao_result(superdao.call_mut().register_member().try_invoke())?;
+            self.superdao_member = true;
+            Ok(())
+        }
+
+        /// Deregister the DAO from the Super DAO.
+        #[cfg(feature = "superdao")]
+        fn deregister_from_superdao(&mut self) -> Result<(), DaoError> {
+            if !self.superdao_member {
+                return Err(DaoError::NotSuperDaoMember);
+            }
+
+            let mut superdao = self.superdao();
+            superdao_result(superdao.call_mut().deregister_member().try_invoke())?;
+            self.superdao_member = false;
+            Ok(())
+        }
+
+        /// Check that `action` can be applied to the DAO as it is.
+        #[cfg(feature = "proposals")]
+        fn check_action(&self, action: &GovernanceAction) -> Result<(), DaoError> {
+            match action {
+                GovernanceAction::UpdateConfig(config) => config.validate(),
+                GovernanceAction::SlashDeposit { voter, amount } => {
+                    if *amount > self.deposit_of(*voter) {
+                        return Err(DaoError::InsufficientDeposit);
+                    }
+                    Ok(())
+                }
+                #[cfg(feature = "superdao")]
+                GovernanceAction::JoinSuperDao | GovernanceAction::LeaveSuperDao => Ok(()),
+                #[cfg(feature = "prevote")]
+                GovernanceAction::SetVotingStrategy(_) => Ok(()),
+                #[cfg(feature = "superdao")]
+                GovernanceAction::MigrateSuperDao(new_superdao) => {
+                    self.ensure_new_superdao(*new_superdao)
+                }
+            }
+        }
+
+        /// Apply the action of an executed proposal, checked again as the DAO may have changed
+        /// since it was proposed.
+        #[cfg(feature = "proposals")]
+        fn apply_action(&mut self, action: GovernanceAction) -> Result<(), DaoError> {
+            self.check_action(&action)?;
+            match action {
+                GovernanceAction::UpdateConfig(config) => {
+                    self.config = config;
+                    self.env().emit_event(ConfigUpdated { config });
+                    Ok(())
+                }
+                GovernanceAction::SlashDeposit { voter, amount } => {
+                    let deposit = self.deposit_of(voter);
+                    if amount == deposit {
+                        self.deposits.remove(voter);
+                    } else {
+                        self.deposits.insert(voter, &(deposit - amount));
+                    }
+                    self.total_deposits -= amount;
+                    self.env().emit_event(DepositSlashed { voter, amount });
+                    Ok(())
+                }
+                #[cfg(feature = "superdao")]
+                GovernanceAction::JoinSuperDao => self.register_in_superdao(),
+                #[cfg(feature = "superdao")]
+                GovernanceAction::LeaveSuperDao => self.deregister_from_superdao(),
+                #[cfg(feature = "superdao")]
+                GovernanceAction::MigrateSuperDao(new_superdao) => {
+                    self.migrate_superdao(new_superdao)
+                }
+                #[cfg(feature = "prevote")]
+                GovernanceAction::SetVotingStrategy(strategy) => {
+                    self.strategy = strategy;
+                    self.env().emit_event(VotingStrategyChanged { strategy });
+                    Ok(())
+                }
+            }
+        }
+
+        /// Move the DAO to `new_superdao`, registering it there if it was a member of the
+        /// current one. The votes on the proposals of the current Super DAO stay keyed by it, so
+        /// they can't be cast on the new one.
+        #[cfg(feature = "superdao")]
+        fn migrate_superdao(&mut self, new_superdao: AccountId) -> Result<(), DaoError> {
+            let was_member = self.superdao_member;
+            if was_member {
+                // The current Super DAO may be gone: leaving it is best effort
+                let mut superdao = self.superdao();
+                let _ = superdao.call_mut().deregister_member().try_invoke();
+                self.superdao_member = false;
+            }
+
+            let old = core::mem::replace(&mut self.superdao, new_superdao);
+            if was_member {
+                self.register_in_superdao()?;
+            }
+
+            self.env().emit_event(SuperDaoMigrated {
+                old,
+                new: new_superdao,
+            });
+            Ok(())
+        }
+
+        /// Reference to the Super DAO contract.
+        #[cfg(feature = "superdao")]
+        fn superdao(&self) -> contract_ref!(SuperDao) {
+            self.superdao.into()
+        }
+
+        /// Create a proposal on the Super DAO sending `message` to `dest`, store it as a
+        /// cross-chain proposal, and return its local ID.
+        #[cfg(feature = "xcm")]
+        fn create_cross_chain_proposal(
+            &mut self,
+            dest: MultiLocation,
+            message: xcm::VersionedXcm<()>,
+            chain_id: u32,
+            call_data: Vec<u8>,
+        ) -> Result<u32, DaoError> {
+            let superdao_id =
+                self.create_superdao_proposal(Call::Chain(ChainCall { dest, message }))?;
+            let proposal_id = self.next_cross_chain_proposal_id;
+            self.cross_chain_proposals.insert(
+                proposal_id,
+                &CrossChainProposal {
+                    superdao: self.superdao,
+                    superdao_id,
+                    status: ProposalStatus::Active,
+                    chain_id,
+                    call_data,
+                    votes_yes: 0,
+                    votes_no: 0,
+                },
+            );
+            self.superdao_proposals
+                .insert((self.superdao, superdao_id), &proposal_id);
+            self.next_cross_chain_proposal_id += 1;
+            Ok(proposal_id)
+        }
+
+        /// Returns the block by which the DAO vote on the Super DAO proposal `proposal_id` must
+        /// be decided: the block before the Super DAO closes the proposal, so that the DAO vote
+        /// can still be cast.
+        #[cfg(feature = "superdao")]
+        fn superdao_voting_end(&self, proposal_id: u32) -> Result<BlockNumber, DaoError> {
+            let proposal = flatten_call(
+                self.superdao()
+                    .call()
+                    .get_proposal(proposal_id)
+                    .try_invoke(),
+            )
+            .map_err(DaoError::SuperDaoCallFailed)?
+            .ok_or(DaoError::ProposalDoesNotExist)?;
+            Ok(proposal.voting_period_end.saturating_sub(1))
+        }
+
+        /// Create a proposal on the Super DAO and open its prevote.
+        #[cfg(feature = "superdao")]
+        fn create_superdao_proposal(&mut self, call: Call) -> Result<u32, DaoError> {
+            let mut superdao = self.superdao();
+            let proposal_id =
+                superdao_result(superdao.call_mut().create_proposal(call).try_invoke())?;
+
+            #[cfg(feature = "prevote")]
+            {
+                let voting_start = self
+                    .env()
+                    .block_number()
+                    .saturating_add(self.config.voting_delay);
+                let deadline = voting_start
+                    .saturating_add(self.config.voting_period)
+                    .min(self.superdao_voting_end(proposal_id)?);
+                self.prevotes.insert(
+                    (self.superdao, proposal_id),
+                    &Prevote {
+                        voting_start,
+                        deadline,
+                        strategy: self.strategy,
+                        snapshot: self.env().block_number().saturating_sub(1),
+                        ..Default::default()
+                    },
+                );
+            }
+
+            self.env().emit_event(ProposalCreated {
+                proposal_id,
+                proposer: self.env().caller(),
+                superdao: true,
+            });
+            Ok(proposal_id)
+        }
+    }
+
+    // Note: `DaoError` can't implement `From` for the errors of the Super DAO and token crates,
+    // which `minidao-common` does not depend on, so they are converted by the functions below.
+
+    /// Returns the value returned by a call to the Super DAO, or why it failed.
+    #[cfg(feature = "superdao")]
+    fn superdao_result<T>(
+        result: Result<ink::MessageResult<Result<T, superdao_traits::Error>>, ink::env::Error>,
+    ) -> Result<T, DaoError> {
+        flatten_call(result)
+            .map_err(DaoError::SuperDaoCallFailed)?
+            .map_err(|error| match error {
+                superdao_traits::Error::AlreadyMember => DaoError::AlreadySuperDaoMember,
+                superdao_traits::Error::NotMember => DaoError::NotSuperDaoMember,
+                superdao_traits::Error::ProposalNotFound => DaoError::ProposalDoesNotExist,
+                superdao_traits::Error::VotingPeriodEnded => DaoError::VotingPeriodEnded,
+                superdao_traits::Error::AlreadyVoted => DaoError::AlreadyVoted,
+                _ => DaoError::SuperDaoRejected,
+            })
+    }
+
+    /// Returns the value returned by a call to the token, or why it failed.
+    #[cfg(feature = "prevote")]
+    fn token_result<T>(
+        result: Result<ink::MessageResult<Result<T, Psp22Error>>, ink::env::Error>,
+    ) -> Result<T, DaoError> {
+        flatten_call(result)
+            .map_err(DaoError::TokenCallFailed)?
+            .map_err(token_error)
+    }
+
+    /// Returns the error of the DAO for an error of the token.
+    #[cfg(feature = "prevote")]
+    fn token_error(error: Psp22Error) -> DaoError {
+        DaoError::from(match error {
+            Psp22Error::Custom(reason) => TokenError::Custom(reason),
+            Psp22Error::InsufficientBalance => TokenError::InsufficientBalance,
+            Psp22Error::InsufficientAllowance => TokenError::InsufficientAllowance,
+            Psp22Error::ZeroRecipientAddress => TokenError::ZeroRecipientAddress,
+            Psp22Error::ZeroSenderAddress => TokenError::ZeroSenderAddress,
+            Psp22Error::SafeTransferCheckFailed(reason) => {
+                TokenError::SafeTransferCheckFailed(reason)
+            }
+        })
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        /// Instantiate the DAO with the constructor of the enabled configuration.
+        fn new_dao(name: &str) -> Dao {
+            new_dao_with_config(name, GovernanceConfig::default()).unwrap()
+        }
+
+        fn new_dao_with_config(name: &str, config: GovernanceConfig) -> Result<Dao, DaoError> {
+            let name = String::from(name);
+            #[cfg(not(feature = "superdao"))]
+            let dao = Dao::new(name, config);
+            #[cfg(all(feature = "superdao", not(feature = "prevote")))]
+            let dao = Dao::new_with_superdao(name, AccountId::from([0x1; 32]), config, false);
+            #[cfg(feature = "prevote")]
+            let dao = Dao::new_with_token(
+                name,
+                AccountId::from([0x1; 32]),
+                AccountId::from([0x2; 32]),
+                config,
+                Strategy::default(),
+                false,
+            );
+            dao
+        }
+
+        /// Advance the chain by `blocks` blocks.
+        fn advance_blocks(blocks: BlockNumber) {
+            for _ in 0..blocks {
+                ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+            }
+        }
+
+        /// Start the proposal, approve it by the vote of the caller, and finalize it.
+        #[cfg(feature = "proposals")]
+        fn pass_proposal(dao: &mut Dao, proposal_id: u32) {
+            assert!(dao.start_proposal(proposal_id).is_ok());
+            advance_blocks(dao.config().voting_delay);
+            assert!(dao.vote(proposal_id, Ballot::Aye).is_ok());
+            advance_blocks(dao.config().voting_period);
+            assert_eq!(
+                dao.finalize_proposal(proposal_id),
+                Ok(ProposalStatus::Succeeded)
+            );
+        }
+
+        #[ink::test]
+        fn test_name() {
+            let dao = new_dao("Test DAO");
+            assert_eq!(dao.get_name(), String::from("Test DAO"));
+        }
+
+        #[cfg(not(feature = "superdao"))]
+        #[ink::test]
+        fn test_default() {
+            let dao = Dao::default();
+            assert_eq!(dao.get_name(), String::from("Default DAO"));
+        }
+
+        #[ink::test]
+        fn test_invalid_config() {
+            let config = GovernanceConfig {
+                approval_threshold: 101,
+                ..Default::default()
+            };
+            assert_eq!(
+                new_dao_with_config("Test DAO", config).err(),
+                Some(DaoError::InvalidGovernanceConfig)
+            );
+        }
+
+        #[ink::test]
+        fn test_empty_name() {
+            let dao = new_dao("");
+            assert_eq!(dao.get_name(), String::from(""));
+        }
+
+        #[ink::test]
+        fn test_long_name() {
+            let long_name = "Very Long DAO Name That Should Still Work";
+            let dao = new_dao(long_name);
+            assert_eq!(dao.get_name(), String::from(long_name));
+        }
+
+        #[cfg(not(feature = "prevote"))]
+        #[ink::test]
+        fn test_voter_registration() {
+            let mut dao = new_dao("TestDAO");
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            // Test registration
+            assert!(dao.register_voter().is_ok());
+            assert!(dao.has_voter(accounts.alice));
+
+            // Test duplicate registration
+            assert_eq!(dao.register_voter(), Err(DaoError::VoterAlreadyRegistered));
+
+            // Test deregistration
+            assert!(dao.deregister_voter().is_ok());
+            assert!(!dao.has_voter(accounts.alice));
+
+            // Test deregistration of non-registered voter
+            assert_eq!(dao.deregister_voter(), Err(DaoError::VoterNotRegistered));
+
+            // Only successful changes are notified, after the admin role granted on instantiation
+            assert_eq!(ink::env::test::recorded_events().count(), 3);
+        }
+
+        #[cfg(not(feature = "prevote"))]
+        #[ink::test]
+        fn test_roles() {
+            let config = GovernanceConfig {
+                registration: Registration::Gated,
+                ..Default::default()
+            };
+            let mut dao = new_dao_with_config("TestDAO", config).unwrap();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            // The deployer is the admin
+            assert!(dao.has_role(accounts.alice, Role::Admin));
+            assert!(!dao.has_role(accounts.bob, Role::Admin));
+
+            // Registration is gated by the member role
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(dao.register_voter(), Err(DaoError::MissingRole));
+            assert_eq!(
+                dao.grant_role(accounts.bob, Role::Member),
+                Err(DaoError::MissingRole)
+            );
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert!(dao.grant_role(accounts.bob, Role::Member).is_ok());
+            assert_eq!(
+                dao.grant_role(accounts.bob, Role::Member),
+                Err(DaoError::RoleAlreadyGranted)
+            );
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(dao.register_voter().is_ok());
+
+            // Revoking the role does not deregister the voter
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert!(dao.revoke_role(accounts.bob, Role::Member).is_ok());
+            assert_eq!(
+                dao.revoke_role(accounts.bob, Role::Member),
+                Err(DaoError::RoleNotGranted)
+            );
+            assert!(!dao.has_role(accounts.bob, Role::Member));
+            assert!(dao.has_voter(accounts.bob));
+
+            // Admin granted on instantiation, then the member role granted and revoked
+            assert_eq!(ink::env::test::recorded_events().count(), 4);
+        }
+
+        #[cfg(not(feature = "prevote"))]
+        #[ink::test]
+        fn test_applications() {
+            let config = GovernanceConfig {
+                quorum: 2,
+                registration: Registration::Application,
+                application_period: 10,
+                ..Default::default()
+            };
+            let mut dao = new_dao_with_config("TestDAO", config).unwrap();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let set_caller = ink::env::test::set_caller::<ink::env::DefaultEnvironment>;
+
+            // Applicants wait for approval
+            for applicant in [accounts.bob, accounts.charlie, accounts.django] {
+                set_caller(applicant);
+                assert_eq!(dao.register_voter(), Err(DaoError::MissingRole));
+                assert_eq!(dao.apply(), Ok(()));
+                assert_eq!(dao.apply(), Err(DaoError::ApplicationPending));
+            }
+            assert_eq!(
+                dao.pending_applications(0, 10),
+                vec![accounts.bob, accounts.charlie, accounts.django]
+            );
+
+            // Only voters and admins review applications, and the admin decides at once
+            assert_eq!(
+                dao.approve_member(accounts.charlie),
+                Err(DaoError::VoterNotRegistered)
+            );
+            set_caller(accounts.alice);
+            assert_eq!(
+                dao.approve_member(accounts.eve),
+                Err(DaoError::ApplicationDoesNotExist)
+            );
+            assert!(dao.approve_member(accounts.bob).is_ok());
+            assert!(dao.has_voter(accounts.bob));
+            assert!(dao.reject_member(accounts.django).is_ok());
+            assert!(!dao.has_voter(accounts.django));
+            assert_eq!(dao.get_application(accounts.django), None);
+            assert_eq!(dao.pending_applications(0, 10), vec![accounts.charlie]);
+
+            // Voters decide once the quorum agrees
+            set_caller(accounts.bob);
+            assert!(dao.approve_member(accounts.charlie).is_ok());
+            assert_eq!(
+                dao.approve_member(accounts.charlie),
+                Err(DaoError::ApplicationAlreadyReviewed)
+            );
+            assert!(!dao.has_voter(accounts.charlie));
+            assert_eq!(
+                dao.get_application(accounts.charlie).map(|a| a.approvals),
+                Some(1)
+            );
+
+            // Applications expire, and can be renewed
+            advance_blocks(11);
+            assert_eq!(
+                dao.approve_member(accounts.charlie),
+                Err(DaoError::ApplicationExpired)
+            );
+            assert_eq!(dao.pending_applications(0, 10), vec![]);
+            set_caller(accounts.charlie);
+            assert!(dao.apply().is_ok());
+            assert_eq!(dao.pending_applications(0, 10), vec![accounts.charlie]);
+            set_caller(accounts.bob);
+            assert!(dao.approve_member(accounts.charlie).is_ok());
+            set_caller(accounts.charlie);
+            assert_eq!(dao.apply(), Err(DaoError::ApplicationPending));
+        }
+
+        #[cfg(all(feature = "proposals", not(feature = "prevote")))]
+        #[ink::test]
+        fn test_deposits() {
+            type Env = ink::env::DefaultEnvironment;
+            let dao_account = AccountId::from([0x9; 32]);
+            ink::env::test::set_callee::<Env>(dao_account);
+            let config = GovernanceConfig {
+                registration_deposit: 10,
+                ..Default::default()
+            };
+            let mut dao = new_dao_with_config("TestDAO", config).unwrap();
+            let accounts = ink::env::test::default_accounts::<Env>();
+
+            // Registration requires the exact deposit
+            ink::env::test::set_caller::<Env>(accounts.bob);
+            ink::env::test::set_value_transferred::<Env>(5);
+            assert_eq!(dao.register_voter(), Err(DaoError::IncorrectDeposit));
+            ink::env::test::set_value_transferred::<Env>(10);
+            assert!(dao.register_voter().is_ok());
+            ink::env::test::set_caller::<Env>(accounts.charlie);
+            assert!(dao.register_voter().is_ok());
+            assert_eq!(dao.deposit_of(accounts.bob), 10);
+            assert_eq!(dao.total_deposits(), 20);
+
+            // Governance slashes deposits through a proposal
+            ink::env::test::set_value_transferred::<Env>(0);
+            assert_eq!(
+                dao.propose_action(GovernanceAction::SlashDeposit {
+                    voter: accounts.bob,
+                    amount: 11
+                }),
+                Err(DaoError::InsufficientDeposit)
+            );
+            let slash = GovernanceAction::SlashDeposit {
+                voter: accounts.bob,
+                amount: 4,
+            };
+            assert_eq!(dao.propose_action(slash), Ok(0));
+            // The deposit is frozen until the proposal is closed
+            ink::env::test::set_caller::<Env>(accounts.bob);
+            assert_eq!(dao.deregister_voter(), Err(DaoError::DepositFrozen));
+            ink::env::test::set_caller::<Env>(accounts.charlie);
+            pass_proposal(&mut dao, 0);
+            assert!(dao.execute(0).is_ok());
+            assert_eq!(dao.deposit_of(accounts.bob), 6);
+            assert_eq!(dao.total_deposits(), 16);
+
+            // Calls can't spend the deposits held
+            ink::env::test::set_account_balance::<Env>(dao_account, 20);
+            let call = ProposalCall {
+                callee: AccountId::from([0x3; 32]),
+                selector: [0xde, 0xad, 0xbe, 0xef],
+                input: vec![],
+                transferred_value: 5,
+                ref_time_limit: 0,
+                allow_reentry: false,
+            };
+            assert_eq!(dao.create_proposal(Some(call)), Ok(1));
+            pass_proposal(&mut dao, 1);
+            assert_eq!(dao.execute(1), Err(DaoError::InsufficientFunds));
+            assert_eq!(
+                dao.get_proposal(1).unwrap().status,
+                ProposalStatus::Succeeded
+            );
+
+            // Deregistration refunds what is left of the deposit
+            ink::env::test::set_account_balance::<Env>(dao_account, 100);
+            let balance = ink::env::test::get_account_balance::<Env>(accounts.bob).unwrap();
+            ink::env::test::set_caller::<Env>(accounts.bob);
+            assert!(dao.deregister_voter().is_ok());
+            assert_eq!(
+                ink::env::test::get_account_balance::<Env>(accounts.bob),
+                Ok(balance + 6)
+            );
+            assert_eq!(dao.deposit_of(accounts.bob), 0);
+            assert_eq!(dao.total_deposits(), 10);
+
+            // Removing a slash proposal releases the deposit too
+            ink::env::test::set_caller::<Env>(accounts.charlie);
+            let slash = GovernanceAction::SlashDeposit {
+                voter: accounts.charlie,
+                amount: 10,
+            };
+            assert_eq!(dao.propose_action(slash), Ok(2));
+            assert_eq!(dao.deregister_voter(), Err(DaoError::DepositFrozen));
+            assert!(dao.remove_proposal(2).is_ok());
+            assert!(dao.deregister_voter().is_ok());
+            assert_eq!(dao.total_deposits(), 0);
+        }
+
+        #[cfg(not(feature = "prevote"))]
+        #[ink::test]
+        fn test_members() {
+            let mut dao = new_dao("TestDAO");
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            for voter in [accounts.alice, accounts.bob, accounts.charlie] {
+                ink::env::test::set_caller::<ink::env::DefaultEnvironment>(voter);
+                assert!(dao.register_voter().is_ok());
+            }
+            assert_eq!(dao.member_count(), 3);
+            assert_eq!(
+                dao.members(0, 10),
+                vec![accounts.alice, accounts.bob, accounts.charlie]
+            );
+            assert_eq!(dao.members(1, 1), vec![accounts.bob]);
+            assert_eq!(dao.members(3, 10), vec![]);
+
+            // The last voter takes the position of the deregistered one
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert!(dao.deregister_voter().is_ok());
+            assert_eq!(dao.member_count(), 2);
+            assert_eq!(dao.members(0, 10), vec![accounts.charlie, accounts.bob]);
+            assert!(!dao.has_voter(accounts.alice));
+
+            // Deregistering the last voter needs no reordering
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(dao.deregister_voter().is_ok());
+            assert_eq!(dao.members(0, 10), vec![accounts.charlie]);
+            assert!(dao.has_voter(accounts.charlie));
+        }
+
+        #[cfg(all(feature = "proposals", not(feature = "prevote")))]
+        #[ink::test]
+        fn test_proposal_management() {
+            let mut dao = new_dao("TestDAO");
+
+            // Register voter
+            assert!(dao.register_voter().is_ok());
+
+            // Create proposal
+            assert_eq!(dao.create_proposal(None), Ok(0));
+
+            // Get proposal
+            let proposal = dao.get_proposal(0);
+            assert!(proposal.is_some());
+            assert_eq!(proposal.unwrap().tally, Tally::default());
+
+            // Proposals can carry a call
+            let call = ProposalCall {
+                callee: AccountId::from([0x3; 32]),
+                selector: [0xde, 0xad, 0xbe, 0xef],
+                input: vec![1, 2, 3],
+                transferred_value: 10,
+                ref_time_limit: 1_000_000,
+                allow_reentry: false,
+            };
+            let no_callee = ProposalCall {
+                callee: AccountId::from([0; 32]),
+                ..call.clone()
+            };
+            assert_eq!(
+                dao.create_proposal(Some(no_callee)),
+                Err(DaoError::InvalidCall)
+            );
+            let self_call = ProposalCall {
+                callee: ink::env::account_id::<ink::env::DefaultEnvironment>(),
+                ..call.clone()
+            };
+            assert_eq!(
+                dao.create_proposal(Some(self_call)),
+                Err(DaoError::InvalidCall)
+            );
+            let reentrant_call = ProposalCall {
+                allow_reentry: true,
+                ..call.clone()
+            };
+            assert_eq!(
+                dao.create_proposal(Some(reentrant_call)),
+                Err(DaoError::InvalidCall)
+            );
+            assert_eq!(dao.create_proposal(Some(call.clone())), Ok(1));
+            assert_eq!(dao.get_proposal(1).unwrap().call, Some(call));
+
+            // Only the proposer can remove the proposal
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(dao.remove_proposal(0), Err(DaoError::NotProposer));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+
+            // Remove proposal
+            assert!(dao.remove_proposal(0).is_ok());
+            assert!(dao.get_proposal(0).is_none());
+
+            // Started proposals can't be removed
+            assert!(dao.start_proposal(1).is_ok());
+            assert_eq!(dao.remove_proposal(1), Err(DaoError::ProposalNotDraft));
+        }
+
+        #[cfg(all(feature = "proposals", not(feature = "prevote")))]
+        #[ink::test]
+        fn test_gated_proposals() {
+            let config = GovernanceConfig {
+                open_proposals: false,
+                ..Default::default()
+            };
+            let mut dao = new_dao_with_config("TestDAO", config).unwrap();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            for voter in [accounts.bob, accounts.alice] {
+                ink::env::test::set_caller::<ink::env::DefaultEnvironment>(voter);
+                assert!(dao.register_voter().is_ok());
+            }
+
+            // Only proposers create proposals
+            assert_eq!(dao.create_proposal(None), Err(DaoError::MissingRole));
+            assert!(dao.grant_role(accounts.alice, Role::Proposer).is_ok());
+            assert_eq!(dao.create_proposal(None), Ok(0));
+
+            // Guardians remove the drafts of others
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(dao.remove_proposal(0), Err(DaoError::NotProposer));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert!(dao.grant_role(accounts.bob, Role::Guardian).is_ok());
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(dao.remove_proposal(0).is_ok());
+        }
+
+        #[cfg(all(feature = "proposals", not(feature = "prevote")))]
+        #[ink::test]
+        fn test_vote() {
+            let mut dao = new_dao("TestDAO");
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            // Register voter
+            assert!(dao.register_voter().is_ok());
+
+            // Create proposal
+            assert!(dao.create_proposal(None).is_ok());
+
+            // Drafts are not open for voting
+            assert_eq!(dao.vote(0, Ballot::Aye), Err(DaoError::ProposalNotActive));
+            assert!(dao.start_proposal(0).is_ok());
+
+            // Vote on proposal
+            assert!(dao.vote(0, Ballot::Aye).is_ok());
+            assert_eq!(dao.vote_count(accounts.alice), 1);
+            assert_eq!(dao.get_vote(0, accounts.alice), Some(Ballot::Aye));
+            assert_eq!(dao.get_vote(0, accounts.bob), None);
+
+            // Check proposal tally
+            let proposal = dao.get_proposal(0).unwrap();
+            assert_eq!(
+                proposal.tally,
+                Tally {
+                    ayes: 1,
+                    nays: 0,
+                    abstains: 0
+                }
+            );
+
+            // Try to vote again on same proposal
+            assert_eq!(dao.vote(0, Ballot::Nay), Err(DaoError::AlreadyVoted));
+
+            // Admin role, registration, creation and vote
+            assert_eq!(ink::env::test::recorded_events().count(), 4);
+        }
+
+        #[cfg(all(feature = "proposals", not(feature = "prevote")))]
+        #[ink::test]
+        fn test_config_update() {
+            type Env = ink::env::DefaultEnvironment;
+            let dao_account = AccountId::from([0x9; 32]);
+            ink::env::test::set_callee::<Env>(dao_account);
+            let mut dao = new_dao("TestDAO");
+            assert_eq!(dao.config(), GovernanceConfig::default());
+
+            // Governance updates the parameters through a proposal, and they must stay valid
+            let accounts = ink::env::test::default_accounts::<Env>();
+            assert!(dao.register_voter().is_ok());
+            let config = GovernanceConfig {
+                voting_delay: 2,
+                voting_period: 5,
+                proposal_deposit: 10,
+                ..Default::default()
+            };
+            assert_eq!(
+                dao.propose_action(GovernanceAction::UpdateConfig(GovernanceConfig {
+                    voting_period: 0,
+                    ..config
+                })),
+                Err(DaoError::InvalidGovernanceConfig)
+            );
+            assert_eq!(
+                dao.propose_action(GovernanceAction::UpdateConfig(config)),
+                Ok(0)
+            );
+            pass_proposal(&mut dao, 0);
+            assert_eq!(dao.config(), GovernanceConfig::default());
+            assert!(dao.execute(0).is_ok());
+            assert_eq!(dao.config(), config);
+
+            // Proposals require the deposit
+            ink::env::test::set_caller::<Env>(accounts.bob);
+            assert!(dao.register_voter().is_ok());
+            assert_eq!(dao.create_proposal(None), Err(DaoError::IncorrectDeposit));
+            ink::env::test::set_value_transferred::<Env>(10);
+            assert_eq!(dao.create_proposal(None), Ok(1));
+            ink::env::test::set_value_transferred::<Env>(0);
+            assert_eq!(dao.get_proposal(1).unwrap().deposit, 10);
+            assert_eq!(dao.proposal_deposits, 10);
+
+            // Voting opens after the delay
+            assert!(dao.start_proposal(1).is_ok());
+            assert_eq!(dao.vote(1, Ballot::Aye), Err(DaoError::VotingNotStarted));
+            advance_blocks(2);
+            assert!(dao.vote(1, Ballot::Aye).is_ok());
+
+            // The deposit is refunded once the proposal is decided
+            ink::env::test::set_account_balance::<Env>(dao_account, 100);
+            let balance = ink::env::test::get_account_balance::<Env>(accounts.bob).unwrap();
+            advance_blocks(5);
+            assert_eq!(dao.finalize_proposal(1), Ok(ProposalStatus::Succeeded));
+            assert_eq!(
+                ink::env::test::get_account_balance::<Env>(accounts.bob),
+                Ok(balance + 10)
+            );
+            assert_eq!(dao.get_proposal(1).unwrap().deposit, 0);
+            assert_eq!(dao.proposal_deposits, 0);
+        }
+
+        #[cfg(not(feature = "prevote"))]
+        #[ink::test]
+        fn test_long_periods_saturate() {
+            let config = GovernanceConfig {
+                registration: Registration::Application,
+                application_period: BlockNumber::MAX,
+                voting_delay: BlockNumber::MAX,
+                voting_period: BlockNumber::MAX,
+                ..Default::default()
+            };
+            let mut dao = new_dao_with_config("TestDAO", config).unwrap();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            advance_blocks(1);
+
+            // Periods past the last block end with it
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(dao.apply().is_ok());
+            assert_eq!(
+                dao.get_application(accounts.bob).map(|a| a.expires_at),
+                Some(BlockNumber::MAX)
+            );
+            #[cfg(feature = "proposals")]
+            {
+                ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+                assert!(dao.approve_member(accounts.bob).is_ok());
+                ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+                assert_eq!(dao.create_proposal(None), Ok(0));
+                assert!(dao.start_proposal(0).is_ok());
+                assert_eq!(dao.get_proposal(0).unwrap().voting_end, BlockNumber::MAX);
+            }
+        }
+
+        #[cfg(all(feature = "proposals", not(feature = "prevote")))]
+        #[ink::test]
+        fn test_proposal_lifecycle() {
+            let config = GovernanceConfig {
+                voting_period: 10,
+                quorum: 2,
+                approval_threshold: 50,
+                execution_period: 5,
+                ..Default::default()
+            };
+            let mut dao = new_dao_with_config("TestDAO", config).unwrap();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            for voter in [accounts.bob, accounts.alice] {
+                ink::env::test::set_caller::<ink::env::DefaultEnvironment>(voter);
+                assert!(dao.register_voter().is_ok());
+            }
+
+            assert_eq!(dao.create_proposal(None), Ok(0));
+            assert_eq!(dao.create_proposal(None), Ok(1));
+            assert_eq!(dao.create_proposal(None), Ok(2));
+            assert_eq!(dao.get_proposal(0).unwrap().status, ProposalStatus::Draft);
+            assert_eq!(dao.finalize_proposal(0), Err(DaoError::ProposalNotActive));
+            for proposal_id in 0..3 {
+                assert!(dao.start_proposal(proposal_id).is_ok());
+            }
+            assert_eq!(dao.start_proposal(0), Err(DaoError::ProposalNotDraft));
+
+            let proposal = dao.get_proposal(0).unwrap();
+            assert_eq!(proposal.status, ProposalStatus::Active);
+            assert_eq!(proposal.voting_end, proposal.voting_start + 10);
+
+            // Proposals 0 and 2 reach the quorum, proposal 1 does not
+            for proposal_id in [0, 1, 2] {
+                assert!(dao.vote(proposal_id, Ballot::Aye).is_ok());
+            }
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(dao.vote(0, Ballot::Aye).is_ok());
+            assert!(dao.vote(2, Ballot::Aye).is_ok());
+            assert_eq!(
+                dao.finalize_proposal(0),
+                Err(DaoError::VotingPeriodNotEnded)
+            );
+            assert_eq!(dao.execute(0), Err(DaoError::ProposalNotSucceeded));
+
+            advance_blocks(10);
+            assert_eq!(dao.vote(1, Ballot::Aye), Err(DaoError::VotingPeriodEnded));
+            assert_eq!(dao.finalize_proposal(0), Ok(ProposalStatus::Succeeded));
+            assert_eq!(dao.finalize_proposal(1), Ok(ProposalStatus::Defeated));
+            assert_eq!(
+                dao.finalize_proposal(1),
+                Err(DaoError::ProposalAlreadyFinalized)
+            );
+            assert_eq!(dao.finalize_proposal(2), Ok(ProposalStatus::Succeeded));
+            assert_eq!(
+                dao.finalize_proposal(2),
+                Err(DaoError::ExecutionPeriodNotEnded)
+            );
+
+            // Succeeded proposals are executed within the execution period
+            assert!(dao.execute(0).is_ok());
+            let proposal = dao.get_proposal(0).unwrap();
+            assert_eq!(proposal.status, ProposalStatus::Executed);
+            // Proposals without a call have no outcome
+            assert_eq!(proposal.outcome, None);
+            assert_eq!(dao.execute(0), Err(DaoError::ProposalNotSucceeded));
+
+            // ... and expire after it
+            advance_blocks(6);
+            assert_eq!(dao.execute(2), Err(DaoError::ExecutionPeriodEnded));
+            assert_eq!(dao.finalize_proposal(2), Ok(ProposalStatus::Expired));
+        }
+
+        #[cfg(all(feature = "proposals", not(feature = "prevote")))]
+        #[ink::test]
+        fn test_ballots() {
+            let config = GovernanceConfig {
+                voting_period: 10,
+                quorum: 2,
+                approval_threshold: 60,
+                execution_period: 5,
+                ..Default::default()
+            };
+            let mut dao = new_dao_with_config("TestDAO", config).unwrap();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            for voter in [accounts.bob, accounts.alice] {
+                ink::env::test::set_caller::<ink::env::DefaultEnvironment>(voter);
+                assert!(dao.register_voter().is_ok());
+            }
+            for proposal_id in 0..3 {
+                assert_eq!(dao.create_proposal(None), Ok(proposal_id));
+                assert!(dao.start_proposal(proposal_id).is_ok());
+            }
+
+            let votes = [
+                (Ballot::Aye, Ballot::Nay),
+                (Ballot::Aye, Ballot::Abstain),
+                (Ballot::Abstain, Ballot::Abstain),
+            ];
+            for (proposal_id, (alice, bob)) in (0..).zip(votes) {
+                ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+                assert!(dao.vote(proposal_id, alice).is_ok());
+                ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+                assert!(dao.vote(proposal_id, bob).is_ok());
+            }
+            assert_eq!(dao.get_vote(0, accounts.alice), Some(Ballot::Aye));
+            assert_eq!(dao.get_vote(0, accounts.bob), Some(Ballot::Nay));
+            assert_eq!(
+                dao.get_proposal(1).unwrap().tally,
+                Tally {
+                    ayes: 1,
+                    nays: 0,
+                    abstains: 1
+                }
+            );
+
+            advance_blocks(10);
+            // Half of the decisive votes is below the approval threshold
+            assert_eq!(dao.finalize_proposal(0), Ok(ProposalStatus::Defeated));
+            // Abstentions count towards the quorum only
+            assert_eq!(dao.finalize_proposal(1), Ok(ProposalStatus::Succeeded));
+            assert_eq!(dao.finalize_proposal(2), Ok(ProposalStatus::Defeated));
+        }
+
+        #[cfg(all(feature = "proposals", not(feature = "prevote")))]
+        #[ink::test]
+        fn test_change_and_retract_vote() {
+            let mut dao = new_dao("TestDAO");
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(dao.register_voter().is_ok());
+            assert_eq!(dao.create_proposal(None), Ok(0));
+            assert!(dao.start_proposal(0).is_ok());
+
+            assert_eq!(dao.change_vote(0, Ballot::Nay), Err(DaoError::NotVoted));
+            assert_eq!(dao.retract_vote(0), Err(DaoError::NotVoted));
+
+            assert!(dao.vote(0, Ballot::Aye).is_ok());
+            assert!(dao.change_vote(0, Ballot::Nay).is_ok());
+            assert_eq!(dao.get_vote(0, accounts.alice), Some(Ballot::Nay));
+            assert_eq!(
+                dao.get_proposal(0).unwrap().tally,
+                Tally {
+                    ayes: 0,
+                    nays: 1,
+                    abstains: 0
+                }
+            );
+
+            assert!(dao.retract_vote(0).is_ok());
+            assert_eq!(dao.get_vote(0, accounts.alice), None);
+            assert_eq!(dao.get_proposal(0).unwrap().tally, Tally::default());
+            assert_eq!(dao.vote_count(accounts.alice), 0);
+
+            // A retracted vote can be cast again, but not changed after the voting period
+            assert!(dao.vote(0, Ballot::Abstain).is_ok());
+            advance_blocks(GovernanceConfig::default().voting_period);
+            assert_eq!(
+                dao.change_vote(0, Ballot::Aye),
+                Err(DaoError::VotingPeriodEnded)
+            );
+            assert_eq!(dao.retract_vote(0), Err(DaoError::VotingPeriodEnded));
+        }
+
+        #[cfg(feature = "superdao")]
+        #[ink::test]
+        fn test_superdao_proposals_require_voter() {
+            let mut dao = new_dao("Test DAO");
+
+            assert_eq!(
+                dao.create_contract_call_proposal(
+                    AccountId::from([0x3; 32]),
+                    [0xde, 0xad, 0xbe, 0xef],
+                    vec![],
+                    0,
+                    0,
+                    false
+                ),
+                Err(DaoError::VoterNotRegistered)
+            );
+            #[cfg(feature = "xcm")]
+            assert_eq!(
+                dao.create_superdao_cross_chain_proposal(1, vec![1, 2, 3, 4]),
+                Err(DaoError::VoterNotRegistered)
+            );
+            #[cfg(feature = "xcm")]
+            assert_eq!(
+                dao.create_xcm_proposal(
+                    parachain(1000),
+                    relay_token(1_000),
+                    XcmWeightLimit::Unlimited,
+                    vec![],
+                    parachain(1000)
+                ),
+                Err(DaoError::VoterNotRegistered)
+            );
+            #[cfg(not(feature = "prevote"))]
+            assert_eq!(
+                dao.vote_proposal(1, Ballot::Aye),
+                Err(DaoError::VoterNotRegistered)
+            );
+        }
+
+        #[cfg(feature = "prevote")]
+        #[ink::test]
+        fn test_delegation_checks() {
+            let mut dao = new_dao("Test DAO");
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert_eq!(
+                dao.delegate(accounts.bob),
+                Err(DaoError::VoterNotRegistered)
+            );
+            assert_eq!(dao.undelegate(), Err(DaoError::NotDelegated));
+            assert_eq!(dao.get_delegation(accounts.alice), None);
+        }
+
+        #[cfg(feature = "prevote")]
+        #[ink::test]
+        fn test_voting_strategy() {
+            let mut dao = new_dao("Test DAO");
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert_eq!(dao.get_voting_strategy(), Strategy::Linear);
+            assert_eq!(
+                dao.get_voting_token(),
+                VotingToken::Contract(AccountId::from([0x2; 32]))
+            );
+
+            // Governance changes the strategy through a proposal. Registering would mint tokens
+            // of the token contract, so the voter is added directly.
+            assert!(dao.voters.add(accounts.alice).is_ok());
+            assert_eq!(
+                dao.propose_action(GovernanceAction::SetVotingStrategy(Strategy::Quadratic)),
+                Ok(0)
+            );
+            pass_proposal(&mut dao, 0);
+            assert_eq!(dao.get_voting_strategy(), Strategy::Linear);
+            assert!(dao.execute(0).is_ok());
+            assert_eq!(dao.get_voting_strategy(), Strategy::Quadratic);
+
+            // Weights of the strategies
+            assert_eq!(Strategy::Linear.weight(100, 0), 100);
+            assert_eq!(Strategy::Quadratic.weight(100, 0), 10);
+            assert_eq!(Strategy::Quadratic.weight(99, 0), 9);
+            assert_eq!(Strategy::OneMemberOneVote.weight(100, 0), 1);
+            assert_eq!(Strategy::Conviction.weight(100, 2), 300);
+            // Delegated tokens count without the conviction of the delegate
+            assert_eq!(Strategy::Linear.weight_with_delegations(100, 50, 0), 150);
+            assert_eq!(Strategy::Quadratic.weight_with_delegations(64, 36, 0), 10);
+            assert_eq!(
+                Strategy::OneMemberOneVote.weight_with_delegations(0, 50, 0),
+                1
+            );
+            assert_eq!(
+                Strategy::Conviction.weight_with_delegations(100, 50, 2),
+                350
+            );
+            assert_eq!(Strategy::Linear.max_conviction(), 0);
+            assert_eq!(Strategy::Conviction.max_conviction(), Conviction::MAX);
+        }
+
+        #[cfg(feature = "prevote")]
+        #[ink::test]
+        fn test_unknown_prevote() {
+            let mut dao = new_dao("Test DAO");
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert_eq!(dao.get_prevote(0), None);
+            assert_eq!(dao.get_prevote_ballot(0, accounts.alice), None);
+            assert_eq!(dao.prevote_voters(0, 0, 10), vec![]);
+            assert_eq!(dao.prevote_result(0), None);
+            assert_eq!(dao.finalize_prevote(0), Err(DaoError::ProposalDoesNotExist));
+            assert_eq!(dao.locked_balance(accounts.alice, 0), 0);
+            assert_eq!(dao.withdraw_locked(0), Err(DaoError::ProposalDoesNotExist));
+        }
+
+        #[cfg(feature = "prevote")]
+        #[ink::test]
+        fn test_prevote_approval() {
+            // The ayes must outweigh the nays under the default threshold
+            let config = GovernanceConfig::default();
+            assert!(!config.is_approved_by_weight(0, 0));
+            assert!(!config.is_approved_by_weight(50, 50));
+            assert!(config.is_approved_by_weight(51, 50));
+            assert!(config.is_approved_by_weight(1, 0));
+
+            // A threshold of 100 requires unanimity
+            let config = GovernanceConfig {
+                approval_threshold: 100,
+                ..Default::default()
+            };
+            assert!(config.is_approved_by_weight(10, 0));
+            assert!(!config.is_approved_by_weight(100, 1));
+        }
+
+        #[cfg(feature = "xcm")]
+        fn parachain(id: u32) -> XcmLocation {
+            XcmLocation {
+                parents: 1,
+                junctions: vec![XcmJunction::Parachain(id)],
+            }
+        }
+
+        #[cfg(feature = "xcm")]
+        fn relay_token(amount: Balance) -> XcmAsset {
+            XcmAsset {
+                id: XcmLocation {
+                    parents: 1,
+                    junctions: vec![],
+                },
+                amount,
+            }
+        }
+
+        #[cfg(feature = "xcm")]
+        #[ink::test]
+        fn test_sync_unknown_proposal() {
+            let mut dao = new_dao("Test DAO");
+            assert_eq!(dao.sync_proposal(0), Err(DaoError::ProposalDoesNotExist));
+        }
+
+        #[cfg(all(feature = "xcm", not(feature = "prevote")))]
+        #[ink::test]
+        fn test_xcm_proposal_validation() {
+            let mut dao = new_dao("Test DAO");
+            assert!(dao.register_voter().is_ok());
+
+            // Nothing to execute on the destination
+            assert_eq!(
+                dao.create_xcm_proposal(
+                    parachain(1000),
+                    relay_token(1_000),
+                    XcmWeightLimit::Unlimited,
+                    vec![],
+                    parachain(1000)
+                ),
+                Err(DaoError::InvalidCrossChainMessage)
+            );
+
+            // More junctions than a location can hold
+            let transact = XcmInstruction::Transact {
+                origin_kind: XcmOriginKind::SovereignAccount,
+                ref_time: 1_000_000_000,
+                proof_size: 1_000_000,
+                call: vec![0, 1],
+            };
+            let too_deep = XcmLocation {
+                parents: 1,
+                junctions: vec![XcmJunction::PalletInstance(50); 9],
+            };
+            assert_eq!(
+                dao.create_xcm_proposal(
+                    too_deep,
+                    relay_token(1_000),
+                    XcmWeightLimit::Unlimited,
+                    vec![transact],
+                    parachain(1000)
+                ),
+                Err(DaoError::InvalidCrossChainMessage)
+            );
+        }
+
+        #[cfg(feature = "xcm")]
+        #[ink::test]
+        fn test_xcm_message_refunds_fee() {
+            let transact = XcmInstruction::Transact {
+                origin_kind: XcmOriginKind::SovereignAccount,
+                ref_time: 1_000_000_000,
+                proof_size: 1_000_000,
+                call: vec![0, 1],
+            };
+            let message = crate::cross_chain::build_message(
+                relay_token(1_000),
+                XcmWeightLimit::Unlimited,
+                vec![transact],
+                parachain(4001),
+            );
+
+            // What is left of the fee goes back to the beneficiary rather than being trapped
+            let fee = MultiAsset {
+                id: Concrete(MultiLocation::parent()),
+                fun: Fungible(1_000),
+            };
+            let expected = xcm::VersionedXcm::V3(Xcm(vec![
+                WithdrawAsset(fee.clone().into()),
+                BuyExecution {
+                    fees: fee,
+                    weight_limit: Unlimited,
+                },
+                Transact {
+                    origin_kind: OriginKind::SovereignAccount,
+                    require_weight_at_most: Weight::from_parts(1_000_000_000, 1_000_000),
+                    call: vec![0, 1].into(),
+                },
+                RefundSurplus,
+                DepositAsset {
+                    assets: Wild(AllCounted(1)),
+                    beneficiary: MultiLocation::new(1, X1(Parachain(4001))),
+                },
+            ]));
+            assert_eq!(message, Ok(expected));
+            assert_eq!(crate::cross_chain::parachain_id(&parachain(4001)), 4001);
+            assert_eq!(crate::cross_chain::parachain_id(&relay_token(0).id), 0);
+        }
+
+        #[cfg(all(feature = "superdao", not(feature = "prevote")))]
+        #[ink::test]
+        fn test_superdao_vote_aggregation() {
+            let config = GovernanceConfig {
+                voting_period: 10,
+                quorum: 3,
+                ..Default::default()
+            };
+            let mut dao = new_dao_with_config("Test DAO", config).unwrap();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(dao.register_voter().is_ok());
+            assert_eq!(
+                dao.finalize_superdao_vote(7),
+                Err(DaoError::ProposalDoesNotExist)
+            );
+
+            // Opening the internal vote reads the Super DAO proposal, which needs a cross-contract
+            // call, so open it directly, ending before the Super DAO proposal
+            dao.superdao_votes.insert(
+                (dao.get_superdao(), 7),
+                &SuperDaoVote {
+                    voting_end: 10,
+                    tally: Tally::default(),
+                    outcome: None,
+                },
+            );
+
+            // Members vote internally, once each, and may abstain
+            assert!(dao.vote_proposal(7, Ballot::Aye).is_ok());
+            assert_eq!(
+                dao.vote_proposal(7, Ballot::Nay),
+                Err(DaoError::AlreadyVoted)
+            );
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert!(dao.register_voter().is_ok());
+            assert!(dao.vote_proposal(7, Ballot::Abstain).is_ok());
+            let vote = dao.get_superdao_vote(7).unwrap();
+            assert_eq!((vote.tally.ayes, vote.tally.abstains), (1, 1));
+            assert_eq!(vote.outcome, None);
+            assert_eq!(
+                dao.finalize_superdao_vote(7),
+                Err(DaoError::VotingPeriodNotEnded)
+            );
+
+            // Without quorum the DAO abstains, exactly once
+            advance_blocks(10);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(dao.register_voter().is_ok());
+            assert_eq!(
+                dao.vote_proposal(7, Ballot::Aye),
+                Err(DaoError::VotingPeriodEnded)
+            );
+            assert_eq!(dao.finalize_superdao_vote(7), Ok(Ballot::Abstain));
+            assert_eq!(
+                dao.get_superdao_vote(7).unwrap().outcome,
+                Some(Ballot::Abstain)
+            );
+            assert_eq!(
+                dao.finalize_superdao_vote(7),
+                Err(DaoError::ProposalAlreadyFinalized)
+            );
+        }
+
+        #[cfg(all(feature = "superdao", not(feature = "prevote")))]
+        #[ink::test]
+        fn test_superdao_membership_through_execute() {
+            let mut dao = new_dao("Test DAO");
+            assert!(!dao.is_superdao_member());
+            assert!(dao.register_voter().is_ok());
+
+            // The DAO can't leave a Super DAO it is not a member of, and the proposal stays
+            // executable
+            assert_eq!(dao.propose_leave_superdao(), Ok(0));
+            assert_eq!(
+                dao.get_proposal(0).unwrap().action,
+                Some(GovernanceAction::LeaveSuperDao)
+            );
+            pass_proposal(&mut dao, 0);
+            assert_eq!(dao.execute(0), Err(DaoError::NotSuperDaoMember));
+            assert_eq!(
+                dao.get_proposal(0).unwrap().status,
+                ProposalStatus::Succeeded
+            );
+            assert!(!dao.is_superdao_member());
+
+            // Joining is proposed the same way
+            assert_eq!(dao.propose_join_superdao(), Ok(1));
+            assert_eq!(
+                dao.get_proposal(1).unwrap().action,
+                Some(GovernanceAction::JoinSuperDao)
+            );
+        }
+
+        #[cfg(all(feature = "superdao", not(feature = "prevote")))]
+        #[ink::test]
+        fn test_superdao_migration() {
+            let mut dao = new_dao("Test DAO");
+            let old = dao.get_superdao();
+            let new = AccountId::from([0x5; 32]);
+            assert!(dao.register_voter().is_ok());
+
+            // Opening the internal vote reads the Super DAO proposal, so open it directly
+            let open_vote = SuperDaoVote {
+                voting_end: u32::MAX,
+                tally: Tally::default(),
+                outcome: None,
+            };
+            dao.superdao_votes.insert((old, 7), &open_vote);
+            assert!(dao.vote_proposal(7, Ballot::Aye).is_ok());
+
+            // The migration is a governance action, checked when proposed
+            assert_eq!(
+                dao.propose_superdao_migration(old),
+                Err(DaoError::InvalidSuperDaoAddress)
+            );
+            assert_eq!(dao.propose_superdao_migration(new), Ok(0));
+            let proposal = dao.get_proposal(0).unwrap();
+            assert_eq!(
+                proposal.action,
+                Some(GovernanceAction::MigrateSuperDao(new))
+            );
+            assert_eq!(proposal.call, None);
+
+            // As the DAO is not a member, executing the proposal only switches address
+            pass_proposal(&mut dao, 0);
+            assert!(dao.execute(0).is_ok());
+            assert_eq!(dao.get_superdao(), new);
+            assert!(!dao.is_superdao_member());
+            // Admin role, registration, internal vote, creation, vote, finalization, migration
+            // and execution
+            assert_eq!(ink::env::test::recorded_events().count(), 8);
+
+            // The internal votes are kept per Super DAO, so a proposal of the new Super DAO
+            // reusing an ID of the old one is voted on afresh
+            assert_eq!(dao.get_superdao_vote(7), None);
+            dao.superdao_votes.insert((new, 7), &open_vote);
+            assert!(dao.vote_proposal(7, Ballot::Nay).is_ok());
+            assert_eq!(dao.get_superdao_vote(7).unwrap().tally.nays, 1);
+            assert_eq!(dao.superdao_votes.get((old, 7)).unwrap().tally.ayes, 1);
+
+            // The address is checked again on execution, as another migration may have been
+            // executed since the proposal
+            for proposal_id in [1, 2] {
+                assert_eq!(dao.propose_superdao_migration(old), Ok(proposal_id));
+                assert!(dao.start_proposal(proposal_id).is_ok());
+                assert!(dao.vote(proposal_id, Ballot::Aye).is_ok());
+            }
+            advance_blocks(GovernanceConfig::default().voting_period);
+            for proposal_id in [1, 2] {
+                assert_eq!(
+                    dao.finalize_proposal(proposal_id),
+                    Ok(ProposalStatus::Succeeded)
+                );
+            }
+            assert!(dao.execute(1).is_ok());
+            assert_eq!(dao.get_superdao(), old);
+            assert_eq!(dao.execute(2), Err(DaoError::InvalidSuperDaoAddress));
+        }
+
+        #[cfg(all(feature = "superdao", not(feature = "prevote")))]
+        #[ink::test]
+        fn test_contract_call_proposal_requires_callee() {
+            let mut dao = new_dao("Test DAO");
+            assert!(dao.register_voter().is_ok());
+
+            assert_eq!(
+                dao.create_contract_call_proposal(
+                    AccountId::from([0; 32]),
+                    [0xde, 0xad, 0xbe, 0xef],
+                    vec![],
+                    0,
+                    0,
+                    false
+                ),
+                Err(DaoError::InvalidCall)
+            );
+        }
+    }
+}
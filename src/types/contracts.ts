@@ -3,11 +3,20 @@ export interface DaoContract {
   register_voter(): Promise<void>;
   deregister_voter(): Promise<void>;
   has_voter(address: string): Promise<boolean>;
-  create_superdao_cross_chain_proposal(): Promise<void>;
-  create_contract_call_proposal(): Promise<void>;
-  vote_proposal(proposalId: number, vote: boolean): Promise<void>;
+  create_superdao_cross_chain_proposal(targetChainId: number, callData: Uint8Array): Promise<number>;
+  create_contract_call_proposal(
+    callee: string,
+    selector: Uint8Array,
+    input: Uint8Array,
+    transferredValue: bigint,
+    refTimeLimit: bigint,
+    allowReentry: boolean,
+  ): Promise<number>;
+  vote_proposal(proposalId: number, ballot: Ballot): Promise<void>;
 }
 
+export type Ballot = 'Aye' | 'Nay' | 'Abstain';
+
 export interface Proposal {
   id: number;
   description: string;